@@ -0,0 +1,79 @@
+use crate::battling::enemy::Enemy;
+
+use macroquad::prelude::*;
+
+const BOOMERANG_SIZE: f32 = 40.;
+const BOOMERANG_SPEED: f32 = 600.;
+// How long the boomerang flies out before coming back
+const BOOMERANG_OUT_TIME: f32 = 0.5;
+
+pub struct Boomerang {
+    pub rect: Rect,
+    pub is_done: bool,
+
+    texture: Texture2D,
+    vel: Vec2,
+    out_timer: f32,
+    rotation: f32,
+}
+
+impl Boomerang {
+    pub async fn new(pos: Vec2, target: Vec2) -> Self {
+        Self {
+            // A rect will represent the boomerang bounds
+            rect: Rect::new(
+                pos.x - BOOMERANG_SIZE / 2.,
+                pos.y - BOOMERANG_SIZE / 2.,
+                BOOMERANG_SIZE,
+                BOOMERANG_SIZE,
+            ),
+            is_done: false,
+
+            texture: load_texture("res/regular/banana.png").await.unwrap(),
+            // Throw the boomerang towards the target
+            vel: (target - pos).normalize_or_zero() * BOOMERANG_SPEED,
+            out_timer: BOOMERANG_OUT_TIME,
+            rotation: 0.,
+        }
+    }
+
+    pub fn update(&mut self, player_pos: Vec2, dt: f32) {
+        self.rotation += dt * 15.;
+
+        if self.out_timer > 0. {
+            self.out_timer -= dt;
+        } else {
+            // Fly back to the player
+            let dir = player_pos - (self.rect.point() + self.rect.size() * 0.5);
+
+            if dir.length() < BOOMERANG_SIZE / 2. {
+                self.is_done = true;
+            }
+
+            self.vel = dir.normalize_or_zero() * BOOMERANG_SPEED;
+        }
+
+        // Update the rect position
+        self.rect.x += dt * self.vel.x;
+        self.rect.y += dt * self.vel.y;
+    }
+
+    pub fn draw(&self) {
+        // Draw the spinning boomerang
+        draw_texture_ex(
+            self.texture,
+            self.rect.x,
+            self.rect.y,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(self.rect.size()),
+                rotation: self.rotation,
+                ..Default::default()
+            },
+        );
+    }
+
+    pub fn is_collision(&self, enemy: &Enemy) -> bool {
+        enemy.rect.overlaps(&self.rect)
+    }
+}
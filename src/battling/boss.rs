@@ -0,0 +1,263 @@
+use crate::battling::bullet::Bullet;
+use crate::battling::obstacle;
+use crate::battling::steering::{self, Boid};
+
+use crate::objects::layout::{self, Anchor};
+
+use macroquad::prelude::*;
+
+const BOSS_SIZE: f32 = 120.;
+const BOSS_BULLET_SPEED: f32 = 250.;
+const CHARGE_SPEED_MULTIPLIER: f32 = 4.;
+const CHARGE_TIME: f32 = 0.5;
+
+pub struct BossDef {
+    pub name: &'static str,
+    pub texture: &'static str,
+    pub health: f32,
+    pub speed: f32,
+    // Unique reward for beating the boss
+    pub reward_money: usize,
+    pub reward_bullets: usize,
+}
+
+// The boss at index i has to be beaten before buying tier i + 1
+pub const BOSSES: [BossDef; 4] = [
+    BossDef {
+        name: "Orange Prince",
+        texture: "res/enemy/orange.png",
+        health: 20.,
+        speed: 60.,
+        reward_money: 50,
+        reward_bullets: 30,
+    },
+    BossDef {
+        name: "Orange King",
+        texture: "res/enemy/orange.png",
+        health: 50.,
+        speed: 75.,
+        reward_money: 200,
+        reward_bullets: 60,
+    },
+    BossDef {
+        name: "Pineapple Queen",
+        texture: "res/enemy/pineapple.png",
+        health: 100.,
+        speed: 90.,
+        reward_money: 500,
+        reward_bullets: 100,
+    },
+    BossDef {
+        name: "The Monkey",
+        texture: "res/monkey.png",
+        health: 200.,
+        speed: 110.,
+        reward_money: 2000,
+        reward_bullets: 200,
+    },
+];
+
+pub struct Boss {
+    pub rect: Rect,
+    pub name: &'static str,
+    pub health: f32,
+    pub max_health: f32,
+    pub reward_money: usize,
+    pub reward_bullets: usize,
+
+    texture: Texture2D,
+    speed: f32,
+    vel: Vec2,
+
+    attack_timer: f32,
+    charge_timer: f32,
+    spiral_angle: f32,
+}
+
+impl Boss {
+    pub async fn new(def: &BossDef) -> Self {
+        Self {
+            // The boss comes in from the top of the screen
+            rect: Rect::new(
+                screen_width() / 2. - BOSS_SIZE / 2.,
+                20.,
+                BOSS_SIZE,
+                BOSS_SIZE,
+            ),
+            name: def.name,
+            health: def.health,
+            max_health: def.health,
+            reward_money: def.reward_money,
+            reward_bullets: def.reward_bullets,
+
+            texture: load_texture(def.texture).await.unwrap(),
+            speed: def.speed,
+            vel: Vec2::ZERO,
+
+            attack_timer: 2.,
+            charge_timer: 0.,
+            spiral_angle: 0.,
+        }
+    }
+
+    // The boss gets more aggressive as it loses health
+    pub fn phase(&self) -> usize {
+        let health = self.health / self.max_health;
+
+        if health > 2. / 3. {
+            1
+        } else if health > 1. / 3. {
+            2
+        } else {
+            3
+        }
+    }
+
+    pub fn damage(&mut self, amount: f32) {
+        self.health -= amount;
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.health > 0.
+    }
+
+    fn center(&self) -> Vec2 {
+        self.rect.point() + self.rect.size() * 0.5
+    }
+
+    // Returns the bullets shot by the boss this frame
+    pub async fn update(&mut self, player_pos: Vec2, obstacles: &[Rect], dt: f32) -> Vec<Bullet> {
+        let mut bullets = Vec::new();
+        let center = self.center();
+        let to_player = (player_pos - center).normalize_or_zero();
+
+        self.attack_timer -= dt;
+
+        if self.charge_timer > 0. {
+            // Keep charging in a straight line
+            self.charge_timer -= dt;
+        } else {
+            // Walk towards the player around the obstacles, faster in the last phase
+            self.vel = steering::steer(
+                Boid {
+                    pos: center,
+                    vel: self.vel,
+                },
+                player_pos,
+                false,
+                &[],
+                obstacles,
+                self.speed * if self.phase() == 3 { 1.5 } else { 1. },
+            );
+        }
+
+        // The boss waits until it can see the player to attack
+        if self.attack_timer <= 0. && obstacle::can_see(center, player_pos, obstacles) {
+            match self.phase() {
+                1 => {
+                    // Shoot a ring of bullets
+                    for i in 0..8 {
+                        let angle = i as f32 / 8. * std::f32::consts::TAU;
+
+                        bullets.push(Self::bullet(center, vec2(angle.cos(), angle.sin())).await);
+                    }
+
+                    self.attack_timer = 2.;
+                }
+                2 => {
+                    // Charge at the player and shoot at them
+                    self.vel = to_player * self.speed * CHARGE_SPEED_MULTIPLIER;
+                    self.charge_timer = CHARGE_TIME;
+
+                    for angle in [-0.2_f32, 0., 0.2] {
+                        bullets.push(
+                            Self::bullet(
+                                center,
+                                vec2(
+                                    to_player.x * angle.cos() - to_player.y * angle.sin(),
+                                    to_player.x * angle.sin() + to_player.y * angle.cos(),
+                                ),
+                            )
+                            .await,
+                        );
+                    }
+
+                    self.attack_timer = 1.5;
+                }
+                _ => {
+                    // Shoot bullets in a spiral
+                    self.spiral_angle += 0.4;
+
+                    for i in 0..2 {
+                        let angle = self.spiral_angle + i as f32 * std::f32::consts::PI;
+
+                        bullets.push(Self::bullet(center, vec2(angle.cos(), angle.sin())).await);
+                    }
+
+                    self.attack_timer = 0.15;
+                }
+            }
+        }
+
+        // Update the rect position
+        self.rect.x += dt * self.vel.x;
+        self.rect.y += dt * self.vel.y;
+
+        bullets
+    }
+
+    async fn bullet(pos: Vec2, dir: Vec2) -> Bullet {
+        let mut bullet = Bullet::with_direction(pos, dir, BOSS_BULLET_SPEED, 1.).await;
+        bullet.color = RED;
+
+        bullet
+    }
+
+    pub fn draw(&self) {
+        // Draw the boss, flashing red while charging
+        draw_texture_ex(
+            self.texture,
+            self.rect.x,
+            self.rect.y,
+            if self.charge_timer > 0. { RED } else { WHITE },
+            DrawTextureParams {
+                dest_size: Some(self.rect.size()),
+                ..Default::default()
+            },
+        );
+    }
+
+    pub fn draw_health_bar(&self, font: Font) {
+        // Half the width of the screen along the bottom
+        let rect = layout::place(
+            Anchor::Bottom,
+            vec2(screen_width() / 2. / layout::scale(), 20.),
+            Vec2::ZERO,
+        );
+        let (pos, size) = (rect.point(), rect.size());
+
+        draw_rectangle(pos.x, pos.y, size.x, size.y, DARKGRAY);
+        draw_rectangle(
+            pos.x,
+            pos.y,
+            size.x * (self.health / self.max_health).max(0.),
+            size.y,
+            RED,
+        );
+        draw_rectangle_lines(pos.x, pos.y, size.x, size.y, 2., BLACK);
+
+        // Draw the boss name above the bar
+        draw_text_ex(
+            format!("{} (Phase {})", self.name, self.phase()).as_str(),
+            pos.x,
+            pos.y - 8.,
+            TextParams {
+                font,
+                font_size: layout::font_size(20),
+                color: BLACK,
+                font_scale: 1.,
+                font_scale_aspect: 1.,
+            },
+        );
+    }
+}
@@ -12,33 +12,29 @@ const BULLET_SIZE: Lazy<Vec2> = Lazy::new(|| {
     ])
 });
 
-const BULLET_SPEED: f32 = 500.;
-
 #[derive(PartialEq, Clone, Copy)]
 pub struct Bullet {
     pub rect: Rect,
+    pub damage: f32,
+    pub color: Color,
     texture: Texture2D,
     vel: Vec2,
 }
 
 impl Bullet {
-    pub async fn new(pos: Vec2) -> Self {
+    pub async fn with_direction(pos: Vec2, dir: Vec2, speed: f32, damage: f32) -> Self {
         Self {
-            // A rect will represent the bullet bounds
-            rect: Rect::new(pos.x, pos.y, BULLET_SIZE[0], BULLET_SIZE[1]),
+            // A rect will represent the bullet bounds, centred on where it is shot from
+            rect: Rect::new(
+                pos.x - BULLET_SIZE[0] / 2.,
+                pos.y - BULLET_SIZE[1] / 2.,
+                BULLET_SIZE[0],
+                BULLET_SIZE[1],
+            ),
+            damage,
+            color: WHITE,
             texture: load_texture("res/bullet.png").await.unwrap(),
-            vel: || -> Vec2 {
-                let dir_x = mouse_position().clone().0 - pos.x;
-                let dir_y = mouse_position().clone().1 - pos.y;
-
-                let factor = BULLET_SPEED / (dir_x.powi(2) + dir_y.powi(2)).sqrt();
-
-                let vel_x = dir_x * factor;
-                let vel_y = dir_y * factor;
-
-                // Return the velocity vector
-                vec2(vel_x, vel_y)
-            }(),
+            vel: dir.normalize_or_zero() * speed,
         }
     }
 
@@ -50,7 +46,7 @@ impl Bullet {
 
     pub fn draw(&self) {
         // Draw the bullet
-        draw_texture(self.texture, self.rect.x, self.rect.y, WHITE);
+        draw_texture(self.texture, self.rect.x, self.rect.y, self.color);
     }
 
     pub fn is_collision(&self, enemy: Enemy) -> bool {
@@ -0,0 +1,113 @@
+use macroquad::prelude::*;
+
+// Trauma lost every second
+const TRAUMA_DECAY: f32 = 1.5;
+// Pixels and degrees the screen moves by at full trauma
+const MAX_SHAKE_OFFSET: f32 = 20.;
+const MAX_SHAKE_ANGLE: f32 = 3.;
+const ZOOM_PUNCH_TIME: f32 = 0.4;
+// How much bigger everything gets at the start of a zoom punch
+const ZOOM_PUNCH_AMOUNT: f32 = 0.15;
+
+// Screen shake, hit-stop and zoom punch drawn over the battle
+pub struct CameraEffects {
+    // From 0 to 1, 0 turns the screen shake off
+    pub shake_strength: f32,
+    pub is_hit_stop_on: bool,
+    pub is_zoom_punch_on: bool,
+
+    // From 0 to 1, the shake gets stronger the more trauma there is
+    trauma: f32,
+    hit_stop_timer: f32,
+    zoom_punch_timer: f32,
+    // Seconds the camera has been running, used to move the shake around
+    time: f32,
+}
+
+impl Default for CameraEffects {
+    fn default() -> Self {
+        Self {
+            shake_strength: 1.,
+            is_hit_stop_on: true,
+            is_zoom_punch_on: true,
+
+            trauma: 0.,
+            hit_stop_timer: 0.,
+            zoom_punch_timer: 0.,
+            time: 0.,
+        }
+    }
+}
+
+impl CameraEffects {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.);
+    }
+
+    // Freeze the battle for a moment
+    pub fn hit_stop(&mut self, seconds: f32) {
+        if self.is_hit_stop_on {
+            self.hit_stop_timer = self.hit_stop_timer.max(seconds);
+        }
+    }
+
+    pub fn zoom_punch(&mut self) {
+        if self.is_zoom_punch_on {
+            self.zoom_punch_timer = ZOOM_PUNCH_TIME;
+        }
+    }
+
+    // Returns the frame time the battle should use, which is 0 during a hit-stop
+    pub fn update(&mut self, dt: f32) -> f32 {
+        self.time += dt;
+        self.trauma = (self.trauma - TRAUMA_DECAY * dt).max(0.);
+        self.zoom_punch_timer = (self.zoom_punch_timer - dt).max(0.);
+
+        if self.hit_stop_timer > 0. {
+            self.hit_stop_timer -= dt;
+
+            return 0.;
+        }
+
+        dt
+    }
+
+    fn camera(&self) -> Camera2D {
+        // Squaring the trauma makes small hits shake a lot less than big ones
+        let shake = self.trauma.powi(2) * self.shake_strength;
+
+        // Overlapping waves move the screen around smoothly without using up random numbers
+        let noise = |speed: f32| (self.time * speed).sin() * (self.time * speed * 0.7).cos();
+
+        let offset = vec2(noise(37.), noise(43.)) * MAX_SHAKE_OFFSET * shake;
+        let rotation = noise(29.) * MAX_SHAKE_ANGLE * shake;
+
+        // The zoom eases back to normal
+        let zoom = 1. + ZOOM_PUNCH_AMOUNT * (self.zoom_punch_timer / ZOOM_PUNCH_TIME).powi(2);
+
+        let mut camera =
+            Camera2D::from_display_rect(Rect::new(0., 0., screen_width(), screen_height()));
+
+        camera.target += offset;
+        camera.zoom *= zoom;
+        camera.rotation = rotation;
+
+        camera
+    }
+
+    // Everything drawn after this is shaken and zoomed, until the default camera is set again
+    pub fn set(&self) {
+        set_camera(&self.camera());
+    }
+
+    // Where the mouse is on the shaken and zoomed field
+    pub fn mouse_position(&self) -> Vec2 {
+        self.camera().screen_to_world(mouse_position().into())
+    }
+
+    pub fn reset(&mut self) {
+        self.trauma = 0.;
+        self.hit_stop_timer = 0.;
+        self.zoom_punch_timer = 0.;
+    }
+}
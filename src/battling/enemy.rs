@@ -9,7 +9,9 @@ const ENEMY_SIZE: Lazy<Vec2> = Lazy::new(|| {
         size("res/enemy/orange.png").unwrap().height as f32,
     ])
 });
-const ENEMY_SPEED: f32 = 250.;
+pub const ENEMY_SPEED: f32 = 250.;
+// Pixels per second an enemy can change its velocity by every second
+const STEERING_FORCE: f32 = 900.;
 
 #[derive(PartialEq, Clone)]
 pub struct Enemy {
@@ -17,12 +19,15 @@ pub struct Enemy {
     pub is_alive: bool,
     pub has_given_money: bool,
     pub texture: Texture2D,
-    vel: Vec2,
+    pub vel: Vec2,
+    pub speed: f32,
     pub worth: i32,
+    pub health: f32,
+    pub max_health: f32,
 }
 
 impl Enemy {
-    pub async fn new(pos: Vec2, player_pos: Vec2) -> Self {
+    pub async fn new(pos: Vec2, player_pos: Vec2, health: f32, speed: f32) -> Self {
         Self {
             // A rect will represent the enemy bounds
             rect: Rect::new(pos.x, pos.y, ENEMY_SIZE[0], ENEMY_SIZE[0]),
@@ -33,7 +38,7 @@ impl Enemy {
                 let dir_x = player_pos.clone().x - pos.x;
                 let dir_y = player_pos.clone().y - pos.y;
 
-                let factor = ENEMY_SPEED / (dir_x.powi(2) + dir_y.powi(2)).sqrt();
+                let factor = speed / (dir_x.powi(2) + dir_y.powi(2)).sqrt();
 
                 let vel_x = dir_x * factor;
                 let vel_y = dir_y * factor;
@@ -41,10 +46,24 @@ impl Enemy {
                 // Return the velocity vector
                 vec2(vel_x, vel_y)
             }(),
+            speed,
             worth: 1,
+            health,
+            max_health: health,
         }
     }
 
+    pub fn center(&self) -> Vec2 {
+        self.rect.point() + self.rect.size() * 0.5
+    }
+
+    // Turn towards the velocity the enemy wants, a little at a time so it doesn't snap around
+    pub fn steer(&mut self, desired: Vec2, dt: f32) {
+        let force = (desired - self.vel).clamp_length_max(STEERING_FORCE * dt);
+
+        self.vel = (self.vel + force).clamp_length_max(self.speed);
+    }
+
     pub fn update(&mut self, dt: f32) {
         if self.is_alive {
             // Update the rect position
@@ -53,6 +72,15 @@ impl Enemy {
         }
     }
 
+    pub fn damage(&mut self, amount: f32) {
+        self.health -= amount;
+
+        // Kill the enemy if it has no health left
+        if self.health <= 0. {
+            self.is_alive = false;
+        }
+    }
+
     pub fn draw(&self) {
         if self.is_alive {
             // Draw the enemy
@@ -0,0 +1,90 @@
+use crate::objects::layout::Anchor;
+use crate::objects::text::Text;
+
+use macroquad::prelude::*;
+
+// The most texts that can float at once, the oldest are reused after that
+const POOL_SIZE: usize = 64;
+// Seconds a text floats for
+const LIFETIME: f32 = 1.;
+// Texts fade out during the last part of their life
+const FADE_TIME: f32 = 0.5;
+// Pixels per second the texts rise by
+const RISE_SPEED: f32 = 50.;
+
+pub const MONEY_COLOR: Color = Color::new(1., 0.85, 0., 1.);
+pub const DAMAGE_COLOR: Color = WHITE;
+pub const AMMO_COLOR: Color = Color::new(0.7, 0.85, 1., 1.);
+
+struct FloatingText {
+    text: Text,
+    pos: Vec2,
+    // Seconds left to float
+    life: f32,
+}
+
+// Numbers that pop up on the field, like money picked up and damage dealt
+pub struct FloatingTexts {
+    pool: Vec<FloatingText>,
+    // Where the next text goes in the pool
+    next: usize,
+}
+
+impl FloatingTexts {
+    pub async fn new() -> Self {
+        // Every text in the pool is a copy of this one, so the font is only loaded once
+        let text = Text::new(
+            Anchor::TopLeft,
+            Vec2::ZERO,
+            "res/Roboto-Medium.ttf".to_string(),
+            String::new(),
+            20,
+            WHITE,
+        )
+        .await;
+
+        let mut pool = Vec::with_capacity(POOL_SIZE);
+
+        for _ in 0..POOL_SIZE {
+            pool.push(FloatingText {
+                text: text.clone(),
+                pos: Vec2::ZERO,
+                life: 0.,
+            });
+        }
+
+        Self { pool, next: 0 }
+    }
+
+    pub fn spawn(&mut self, text: String, pos: Vec2, color: Color) {
+        let floating_text = &mut self.pool[self.next];
+
+        floating_text.text.change(text);
+        floating_text.text.color = color;
+        floating_text.pos = pos;
+        floating_text.life = LIFETIME;
+
+        self.next = (self.next + 1) % POOL_SIZE;
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        for floating_text in self.pool.iter_mut().filter(|text| text.life > 0.) {
+            floating_text.life -= dt;
+            floating_text.pos.y -= RISE_SPEED * dt;
+
+            floating_text.text.color.a = (floating_text.life / FADE_TIME).min(1.);
+        }
+    }
+
+    pub fn draw(&self) {
+        for floating_text in self.pool.iter().filter(|text| text.life > 0.) {
+            floating_text.text.draw_at(floating_text.pos);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for floating_text in &mut self.pool {
+            floating_text.life = 0.;
+        }
+    }
+}
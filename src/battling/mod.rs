@@ -1,3 +1,12 @@
+pub mod boomerang;
+pub mod boss;
 pub mod bullet;
+pub mod camera;
 pub mod enemy;
+pub mod floating_text;
+pub mod obstacle;
+pub mod particles;
+pub mod pickup;
 pub mod player;
+pub mod power_up;
+pub mod steering;
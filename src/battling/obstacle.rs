@@ -0,0 +1,231 @@
+use crate::objects::data_file::{self, parse_vec2, ParseError};
+
+use macroquad::file::load_string;
+use macroquad::prelude::*;
+
+// One of these is picked at the start of every run
+pub const MAPS: [&str; 4] = [
+    "res/maps/open.txt",
+    "res/maps/crates.txt",
+    "res/maps/orchard.txt",
+    "res/maps/stumps.txt",
+];
+
+const CRATE_COLOR: Color = Color::new(0.65, 0.45, 0.2, 1.);
+const WOOD_COLOR: Color = Color::new(0.4, 0.25, 0.1, 1.);
+const BOWL_COLOR: Color = Color::new(0.92, 0.92, 0.96, 1.);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ObstacleKind {
+    Crate,
+    FruitBowl,
+    TreeStump,
+}
+
+impl ObstacleKind {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "crate" => Some(Self::Crate),
+            "fruit_bowl" => Some(Self::FruitBowl),
+            "tree_stump" => Some(Self::TreeStump),
+            _ => None,
+        }
+    }
+
+    pub fn size(self) -> Vec2 {
+        match self {
+            Self::Crate => vec2(60., 60.),
+            Self::FruitBowl => vec2(70., 45.),
+            Self::TreeStump => vec2(80., 80.),
+        }
+    }
+
+    // Tree stumps can't be broken
+    pub fn health(self) -> Option<f32> {
+        match self {
+            Self::Crate => Some(6.),
+            Self::FruitBowl => Some(3.),
+            Self::TreeStump => None,
+        }
+    }
+}
+
+// Something on the field that blocks the player, enemies, bullets and sight
+#[derive(Clone)]
+pub struct Obstacle {
+    pub rect: Rect,
+    pub kind: ObstacleKind,
+    pub health: Option<f32>,
+}
+
+impl Obstacle {
+    pub fn new(kind: ObstacleKind, center: Vec2) -> Self {
+        Self {
+            rect: Rect::new(
+                center.x - kind.size().x / 2.,
+                center.y - kind.size().y / 2.,
+                kind.size().x,
+                kind.size().y,
+            ),
+            kind,
+            health: kind.health(),
+        }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        self.rect.point() + self.rect.size() * 0.5
+    }
+
+    pub fn damage(&mut self, amount: f32) {
+        if let Some(health) = &mut self.health {
+            *health -= amount;
+        }
+    }
+
+    pub fn is_destroyed(&self) -> bool {
+        self.health.is_some_and(|health| health <= 0.)
+    }
+
+    pub fn draw(&self) {
+        let Rect { x, y, w, h } = self.rect;
+
+        // Broken obstacles get darker the closer they are to breaking
+        let shade = match (self.health, self.kind.health()) {
+            (Some(health), Some(max_health)) => 0.6 + 0.4 * (health / max_health).max(0.),
+            _ => 1.,
+        };
+        let shaded =
+            |color: Color| Color::new(color.r * shade, color.g * shade, color.b * shade, 1.);
+
+        match self.kind {
+            ObstacleKind::Crate => {
+                draw_rectangle(x, y, w, h, shaded(CRATE_COLOR));
+                draw_rectangle_lines(x, y, w, h, 4., shaded(WOOD_COLOR));
+
+                // Planks across the crate
+                draw_line(x, y, x + w, y + h, 3., shaded(WOOD_COLOR));
+                draw_line(x + w, y, x, y + h, 3., shaded(WOOD_COLOR));
+            }
+            ObstacleKind::FruitBowl => {
+                // Fruit sticking out of the top of the bowl
+                let radius = h * 0.25;
+
+                for (i, color) in [ORANGE, RED, YELLOW].into_iter().enumerate() {
+                    draw_circle(
+                        x + w * (0.25 + i as f32 * 0.25),
+                        y + radius,
+                        radius,
+                        shaded(color),
+                    );
+                }
+
+                draw_rectangle(x, y + h * 0.4, w, h * 0.6, shaded(BOWL_COLOR));
+                draw_rectangle_lines(x, y + h * 0.4, w, h * 0.6, 2., shaded(GRAY));
+            }
+            ObstacleKind::TreeStump => {
+                let center = self.center();
+
+                draw_circle(center.x, center.y, w / 2., WOOD_COLOR);
+                draw_circle(center.x, center.y, w / 2. - 6., CRATE_COLOR);
+
+                // Tree rings
+                for ring in [0.25, 0.5] {
+                    draw_circle_lines(center.x, center.y, w * ring / 2., 2., WOOD_COLOR);
+                }
+            }
+        }
+    }
+}
+
+// The obstacles of a map, where their centres go as a fraction of the screen
+pub type Map = Vec<(ObstacleKind, Vec2)>;
+
+// A map line is the kind of obstacle and where its centre goes, like crate=0.3,0.3
+pub fn parse_map(file: &str) -> (Map, Vec<ParseError>) {
+    let mut map = Vec::new();
+    let (entries, mut errors) = data_file::parse(file);
+
+    for entry in entries {
+        match (ObstacleKind::parse(entry.key), parse_vec2(entry.value)) {
+            (Some(kind), Some(pos)) => map.push((kind, pos)),
+            (None, _) => errors.push(entry.error("unknown obstacle")),
+            (_, None) => errors.push(entry.error("expected x,y")),
+        }
+    }
+
+    (map, errors)
+}
+
+pub async fn load_maps() -> Vec<Map> {
+    let mut maps = Vec::new();
+
+    for path in MAPS {
+        let (map, errors) = parse_map(load_string(path).await.unwrap().as_str());
+        data_file::report(path, &errors);
+
+        maps.push(map);
+    }
+
+    maps
+}
+
+// The obstacles of a map at the size the screen is now, so a resized window still fits
+pub fn place_map(map: &Map) -> Vec<Obstacle> {
+    let screen = vec2(screen_width(), screen_height());
+
+    map.iter()
+        .map(|&(kind, pos)| Obstacle::new(kind, pos * screen))
+        .collect()
+}
+
+// How far to move a rect so it stops overlapping an obstacle, the shortest way out
+pub fn push_out(rect: Rect, obstacle: Rect) -> Vec2 {
+    let Some(overlap) = rect.intersect(obstacle) else {
+        return Vec2::ZERO;
+    };
+
+    let away = (rect.point() + rect.size() * 0.5) - (obstacle.point() + obstacle.size() * 0.5);
+
+    if overlap.w < overlap.h {
+        vec2(overlap.w * away.x.signum(), 0.)
+    } else {
+        vec2(0., overlap.h * away.y.signum())
+    }
+}
+
+// Whether a straight line between two points misses every obstacle
+pub fn can_see(from: Vec2, to: Vec2, obstacles: &[Rect]) -> bool {
+    !obstacles
+        .iter()
+        .any(|obstacle| is_line_through(from, to, *obstacle))
+}
+
+fn is_line_through(from: Vec2, to: Vec2, rect: Rect) -> bool {
+    let dir = to - from;
+    // How far along the line it enters and leaves the rect, from 0 to 1
+    let (mut enter, mut leave) = (0_f32, 1_f32);
+
+    for (start, dir, min, max) in [
+        (from.x, dir.x, rect.left(), rect.right()),
+        (from.y, dir.y, rect.top(), rect.bottom()),
+    ] {
+        if dir == 0. {
+            // The line runs alongside the rect on this axis
+            if start < min || start > max {
+                return false;
+            }
+        } else {
+            let a = (min - start) / dir;
+            let b = (max - start) / dir;
+
+            enter = enter.max(a.min(b));
+            leave = leave.min(a.max(b));
+
+            if enter > leave {
+                return false;
+            }
+        }
+    }
+
+    true
+}
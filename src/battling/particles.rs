@@ -0,0 +1,202 @@
+use macroquad::prelude::*;
+
+// The most particles that can be alive at once, the oldest are reused after that
+const POOL_SIZE: usize = 1000;
+
+// How a burst of particles looks and moves
+#[derive(Clone, Copy)]
+pub struct Emitter {
+    pub count: usize,
+    // (Min, Max) seconds a particle lives for
+    pub lifetime: (f32, f32),
+    // (Min, Max) pixels per second
+    pub speed: (f32, f32),
+    // Radians the particles spread out by around the direction they are emitted in
+    pub spread: f32,
+    // (Min, Max) radius in pixels
+    pub size: (f32, f32),
+    // Particles fade from the start colour to the end colour over their life
+    pub start_color: Color,
+    pub end_color: Color,
+    // Pixels per second added to the downwards speed every second
+    pub gravity: f32,
+}
+
+// Juice flying out of a killed fruit, the colour is set per fruit
+pub const JUICE: Emitter = Emitter {
+    count: 16,
+    lifetime: (0.3, 0.7),
+    speed: (80., 260.),
+    spread: std::f32::consts::TAU,
+    size: (2., 5.),
+    start_color: ORANGE,
+    end_color: Color::new(1., 0.63, 0., 0.),
+    gravity: 400.,
+};
+
+pub const MUZZLE_SMOKE: Emitter = Emitter {
+    count: 4,
+    lifetime: (0.2, 0.5),
+    speed: (20., 60.),
+    spread: 0.8,
+    size: (3., 6.),
+    start_color: Color::new(0.8, 0.8, 0.8, 0.6),
+    end_color: Color::new(0.5, 0.5, 0.5, 0.),
+    gravity: -60.,
+};
+
+pub const COIN_SPARKLE: Emitter = Emitter {
+    count: 10,
+    lifetime: (0.2, 0.5),
+    speed: (40., 140.),
+    spread: std::f32::consts::TAU,
+    size: (1., 3.),
+    start_color: Color::new(1., 1., 0.6, 1.),
+    end_color: Color::new(1., 0.8, 0., 0.),
+    gravity: 0.,
+};
+
+pub const DEATH_EXPLOSION: Emitter = Emitter {
+    count: 60,
+    lifetime: (0.4, 1.),
+    speed: (100., 400.),
+    spread: std::f32::consts::TAU,
+    size: (3., 8.),
+    start_color: Color::new(1., 0.9, 0.3, 1.),
+    end_color: Color::new(0.6, 0.1, 0., 0.),
+    gravity: 150.,
+};
+
+// Bits of wood flying off a broken crate
+pub const SPLINTERS: Emitter = Emitter {
+    count: 20,
+    lifetime: (0.3, 0.8),
+    speed: (60., 240.),
+    spread: std::f32::consts::TAU,
+    size: (2., 4.),
+    start_color: Color::new(0.65, 0.45, 0.2, 1.),
+    end_color: Color::new(0.4, 0.25, 0.1, 0.),
+    gravity: 300.,
+};
+
+#[derive(Clone, Copy)]
+struct Particle {
+    pos: Vec2,
+    vel: Vec2,
+    size: f32,
+    // Seconds left to live
+    life: f32,
+    lifetime: f32,
+    start_color: Color,
+    end_color: Color,
+    gravity: f32,
+}
+
+impl Particle {
+    fn is_alive(&self) -> bool {
+        self.life > 0.
+    }
+}
+
+fn lerp_color(from: Color, to: Color, amount: f32) -> Color {
+    Color::new(
+        from.r + (to.r - from.r) * amount,
+        from.g + (to.g - from.g) * amount,
+        from.b + (to.b - from.b) * amount,
+        from.a + (to.a - from.a) * amount,
+    )
+}
+
+pub struct Particles {
+    // From 0 to 1, how many of an emitter's particles are spawned
+    pub density: f32,
+
+    pool: Vec<Particle>,
+    // Where the next particle goes in the pool
+    next: usize,
+    // Particles use their own random numbers so they don't change a seeded run
+    seed: u32,
+}
+
+impl Default for Particles {
+    fn default() -> Self {
+        Self {
+            density: 1.,
+
+            pool: Vec::with_capacity(POOL_SIZE),
+            next: 0,
+            seed: 0x9e37_79b9,
+        }
+    }
+}
+
+impl Particles {
+    // A random number between low and high
+    fn random(&mut self, low: f32, high: f32) -> f32 {
+        // Xorshift
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+
+        low + (high - low) * (self.seed as f32 / u32::MAX as f32)
+    }
+
+    // Send out a burst of particles towards a direction
+    pub fn emit(&mut self, emitter: &Emitter, pos: Vec2, dir: Vec2) {
+        let count = (emitter.count as f32 * self.density).round() as usize;
+        let angle = dir.y.atan2(dir.x);
+
+        for _ in 0..count {
+            let angle = angle + self.random(-0.5, 0.5) * emitter.spread;
+            let lifetime = self.random(emitter.lifetime.0, emitter.lifetime.1);
+            let speed = self.random(emitter.speed.0, emitter.speed.1);
+
+            let particle = Particle {
+                pos,
+                vel: vec2(angle.cos(), angle.sin()) * speed,
+                size: self.random(emitter.size.0, emitter.size.1),
+                life: lifetime,
+                lifetime,
+                start_color: emitter.start_color,
+                end_color: emitter.end_color,
+                gravity: emitter.gravity,
+            };
+
+            // Fill the pool up first, then reuse the oldest particles
+            if self.pool.len() < POOL_SIZE {
+                self.pool.push(particle);
+            } else {
+                self.pool[self.next] = particle;
+            }
+
+            self.next = (self.next + 1) % POOL_SIZE;
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        for particle in self.pool.iter_mut().filter(|particle| particle.is_alive()) {
+            particle.life -= dt;
+            particle.vel.y += particle.gravity * dt;
+            particle.pos += particle.vel * dt;
+        }
+    }
+
+    pub fn draw(&self) {
+        for particle in self.pool.iter().filter(|particle| particle.is_alive()) {
+            // How far through its life the particle is, from 0 to 1
+            let age = 1. - particle.life / particle.lifetime;
+
+            draw_circle(
+                particle.pos.x,
+                particle.pos.y,
+                particle.size * (1. - age * 0.5),
+                lerp_color(particle.start_color, particle.end_color, age),
+            );
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.pool.clear();
+        self.next = 0;
+    }
+}
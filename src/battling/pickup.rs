@@ -0,0 +1,95 @@
+use macroquad::prelude::*;
+
+const PICKUP_SIZE: f32 = 24.;
+const PICKUP_SPEED: f32 = 400.;
+// Seconds before an uncollected pickup disappears
+const DESPAWN_TIME: f32 = 8.;
+// Pickups start blinking when they are about to disappear
+const BLINK_TIME: f32 = 2.;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum PickupKind {
+    Coin(usize),
+    Ammo(usize),
+}
+
+pub struct Pickup {
+    pub rect: Rect,
+    pub kind: PickupKind,
+    pub is_collected: bool,
+
+    bullet_texture: Texture2D,
+    despawn_timer: f32,
+}
+
+impl Pickup {
+    pub async fn new(pos: Vec2, kind: PickupKind) -> Self {
+        Self {
+            // A rect will represent the pickup bounds
+            rect: Rect::new(
+                pos.x - PICKUP_SIZE / 2.,
+                pos.y - PICKUP_SIZE / 2.,
+                PICKUP_SIZE,
+                PICKUP_SIZE,
+            ),
+            kind,
+            is_collected: false,
+
+            bullet_texture: load_texture("res/bullet.png").await.unwrap(),
+            despawn_timer: DESPAWN_TIME,
+        }
+    }
+
+    pub fn update(&mut self, player_pos: Vec2, magnet_radius: f32, dt: f32) {
+        self.despawn_timer -= dt;
+
+        // Pull the pickup towards the player when it is close enough
+        let dir = player_pos - (self.rect.point() + self.rect.size() * 0.5);
+
+        if dir.length() < magnet_radius {
+            let vel = dir.normalize_or_zero() * PICKUP_SPEED;
+
+            self.rect.x += dt * vel.x;
+            self.rect.y += dt * vel.y;
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.despawn_timer <= 0.
+    }
+
+    pub fn draw(&self) {
+        // Blink before disappearing
+        if self.despawn_timer < BLINK_TIME && (get_time() * 8.) as i32 % 2 == 0 {
+            return;
+        }
+
+        let center = self.rect.point() + self.rect.size() * 0.5;
+
+        match self.kind {
+            PickupKind::Coin(_) => {
+                // Draw a coin
+                draw_circle(center.x, center.y, PICKUP_SIZE / 2., GOLD);
+                draw_circle_lines(center.x, center.y, PICKUP_SIZE / 2., 2., ORANGE);
+            }
+            PickupKind::Ammo(_) => {
+                // Draw an ammo crate
+                draw_rectangle(self.rect.x, self.rect.y, self.rect.w, self.rect.h, BROWN);
+                draw_rectangle_lines(
+                    self.rect.x,
+                    self.rect.y,
+                    self.rect.w,
+                    self.rect.h,
+                    2.,
+                    BLACK,
+                );
+                draw_texture(
+                    self.bullet_texture,
+                    center.x - self.bullet_texture.width() / 2.,
+                    center.y - self.bullet_texture.height() / 2.,
+                    WHITE,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::objects::layout::{self, Anchor};
+
+use macroquad::prelude::*;
+
+pub const ABILITY_KEY: KeyCode = KeyCode::Space;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum AbilityKind {
+    None,
+    Dash,
+    ShieldBurst,
+    SpikyNova,
+    BananaBoomerang,
+}
+
+impl AbilityKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AbilityKind::None => "None",
+            AbilityKind::Dash => "Dash",
+            AbilityKind::ShieldBurst => "Shield Burst",
+            AbilityKind::SpikyNova => "Spiky Nova",
+            AbilityKind::BananaBoomerang => "Banana Boomerang",
+        }
+    }
+
+    // Seconds until the ability can be used again
+    fn cooldown(&self) -> f32 {
+        match self {
+            AbilityKind::None => 0.,
+            AbilityKind::Dash => 2.,
+            AbilityKind::ShieldBurst => 8.,
+            AbilityKind::SpikyNova => 6.,
+            AbilityKind::BananaBoomerang => 3.,
+        }
+    }
+
+    // Seconds the ability stays active after it is used
+    pub fn duration(&self) -> f32 {
+        match self {
+            AbilityKind::Dash => 0.2,
+            AbilityKind::ShieldBurst => 2.,
+            _ => 0.,
+        }
+    }
+}
+
+pub struct Ability {
+    pub kind: AbilityKind,
+
+    cooldown_timer: f32,
+    active_timer: f32,
+}
+
+impl Ability {
+    pub fn new(kind: AbilityKind) -> Self {
+        Self {
+            kind,
+            cooldown_timer: 0.,
+            active_timer: 0.,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.cooldown_timer = (self.cooldown_timer - dt).max(0.);
+        self.active_timer = (self.active_timer - dt).max(0.);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.kind != AbilityKind::None && self.cooldown_timer <= 0.
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active_timer > 0.
+    }
+
+    // Returns true on the frame the ability is used
+    pub fn trigger(&mut self) -> bool {
+        if is_key_pressed(ABILITY_KEY) && self.is_ready() {
+            self.cooldown_timer = self.kind.cooldown();
+            self.active_timer = self.kind.duration();
+
+            return true;
+        }
+
+        false
+    }
+
+    pub fn reset(&mut self) {
+        self.cooldown_timer = 0.;
+        self.active_timer = 0.;
+    }
+
+    pub fn draw(&self, font: Font) {
+        if self.kind == AbilityKind::None {
+            return;
+        }
+
+        let rect = layout::place(Anchor::BottomLeft, vec2(200., 30.), Vec2::ZERO);
+        let (pos, size) = (rect.point(), rect.size());
+
+        // Fraction of the cooldown that has passed
+        let progress = 1. - self.cooldown_timer / self.kind.cooldown();
+
+        // Draw cooldown bar
+        draw_rectangle(pos.x, pos.y, size.x, size.y, GRAY);
+        draw_rectangle(
+            pos.x,
+            pos.y,
+            size.x * progress,
+            size.y,
+            if self.is_ready() { GOLD } else { LIGHTGRAY },
+        );
+        draw_rectangle_lines(pos.x, pos.y, size.x, size.y, 2., BLACK);
+
+        // Draw ability name
+        layout::draw_text_centered(
+            if self.is_ready() {
+                format!("[Space] {}", self.kind.name())
+            } else {
+                format!("{} ({:.1}s)", self.kind.name(), self.cooldown_timer)
+            }
+            .as_str(),
+            rect,
+            font,
+            layout::font_size(15),
+            BLACK,
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::objects::animation::{Animation, AnimationData};
+
 use imagesize::size;
 use macroquad::prelude::*;
 use once_cell::sync::Lazy;
@@ -9,60 +11,207 @@ const GUN_SIZE: Lazy<Vec2> = Lazy::new(|| {
         size("res/gun.png").unwrap().height as f32,
     ])
 });
+const MUZZLE_FLASH_SIZE: f32 = 24.;
+// Pixels from the middle of the player to the back of the gun
+const HOLD_DISTANCE: f32 = 20.;
 
 pub struct Gun {
-    pub rect: Rect,
+    // Where the gun is held, it turns around this point
+    pub pivot: Vec2,
+    // Where the gun is aimed, set to the mouse on the field every frame
+    pub target: Vec2,
     pub texture: Texture2D,
     pub can_shoot: bool,
-    pub bullets: usize,
+    // Seconds until the next bullet can be shot
+    pub cooldown: f32,
+
+    pub magazine: usize,
+    pub magazine_size: usize,
+    // The magazine is filled instantly when the battle starts
+    pub needs_load: bool,
+
+    reload_timer: f32,
+    reload_time: f32,
+
+    muzzle_flash: Animation,
+    muzzle_flash_texture: Texture2D,
 }
 
 impl Gun {
     pub async fn new() -> Self {
         Self {
-            // A rect will represent the gun bounds
-            rect: Rect::new(
-                screen_width() / 2. - GUN_SIZE[0] / 2.,
-                screen_height() / 2. - GUN_SIZE[1] / 2.,
-                GUN_SIZE[0],
-                GUN_SIZE[1],
-            ),
+            pivot: vec2(screen_width() / 2., screen_height() / 2.),
+            target: Vec2::ZERO,
             texture: load_texture("res/gun.png").await.unwrap(),
             can_shoot: false,
-            bullets: 0,
+            cooldown: 0.,
+
+            magazine: 0,
+            magazine_size: 0,
+            needs_load: true,
+
+            reload_timer: 0.,
+            reload_time: 0.,
+
+            // The flash only shows after a shot
+            muzzle_flash: Animation::stopped(
+                &AnimationData::load("res/animations/muzzle_flash.txt").await,
+            ),
+            muzzle_flash_texture: load_texture("res/muzzle_flash.png").await.unwrap(),
+        }
+    }
+
+    pub fn update(&mut self, player_pos: Vec2, dt: f32, reserve: &mut usize) {
+        self.cooldown = (self.cooldown - dt).max(0.);
+
+        if self.reload_timer > 0. {
+            self.reload_timer -= dt;
+
+            // Fill the magazine once the reload is done
+            if self.reload_timer <= 0. {
+                self.load(reserve);
+            }
         }
+
+        self.pivot = player_pos;
+
+        self.muzzle_flash.update(dt);
     }
 
-    pub fn update(&mut self, player_pos: Vec2) {
-        self.rect.x = player_pos.x;
-        self.rect.y = player_pos.y;
+    // Show the muzzle flash for a shot
+    pub fn flash(&mut self) {
+        self.muzzle_flash.restart();
+    }
+
+    // Unit vector from the pivot to the target
+    pub fn direction(&self) -> Vec2 {
+        let dir = self.target - self.pivot;
+
+        // Point right if the target is right on top of the pivot
+        if dir.length() > 0. {
+            dir.normalize()
+        } else {
+            vec2(1., 0.)
+        }
+    }
+
+    pub fn is_aiming_left(&self) -> bool {
+        self.direction().x < 0.
+    }
+
+    // The tip of the barrel, where bullets come out
+    pub fn muzzle(&self) -> Vec2 {
+        self.pivot + self.direction() * (HOLD_DISTANCE + GUN_SIZE[0])
     }
 
     pub fn draw(&mut self) {
-        // Draw the gun
+        // Point the gun to the mouse
+        let dir = self.direction();
+        let rotation = dir.y.atan2(dir.x);
+
+        // Draw the gun in front of the pivot, turned around the pivot and
+        // flipped so it isn't upside down when aiming left
         draw_texture_ex(
             self.texture,
-            self.rect.x,
-            self.rect.y,
+            self.pivot.x + HOLD_DISTANCE,
+            self.pivot.y - GUN_SIZE[1] / 2.,
             WHITE,
             DrawTextureParams {
-                // Point the gun to the mouse
-                rotation: (libm::atan2(
-                    (mouse_position().1 - self.rect.y) as f64,
-                    (mouse_position().0 - self.rect.x) as f64,
-                ) * (180. / std::f64::consts::PI))
-                    .to_radians() as f32,
+                rotation,
+                flip_y: self.is_aiming_left(),
+                pivot: Some(self.pivot),
                 ..Default::default()
             },
         );
+
+        // Draw the muzzle flash at the end of the barrel
+        let muzzle = self.muzzle();
+
+        self.muzzle_flash.draw(
+            self.muzzle_flash_texture,
+            Rect::new(
+                muzzle.x - MUZZLE_FLASH_SIZE * 0.5,
+                muzzle.y - MUZZLE_FLASH_SIZE * 0.5,
+                MUZZLE_FLASH_SIZE,
+                MUZZLE_FLASH_SIZE,
+            ),
+            WHITE,
+            rotation,
+            false,
+        );
+
+        if self.is_reloading() {
+            self.draw_reload_progress();
+        }
+    }
+
+    fn draw_reload_progress(&self) {
+        const RADIUS: f32 = 35.;
+        const SEGMENTS: usize = 32;
+
+        let center = self.pivot;
+        let progress = 1. - self.reload_timer / self.reload_time;
+
+        draw_circle_lines(center.x, center.y, RADIUS, 2., Color::new(0., 0., 0., 0.3));
+
+        // Draw an arc that fills up clockwise from the top
+        for i in 0..(SEGMENTS as f32 * progress) as usize {
+            let start =
+                i as f32 / SEGMENTS as f32 * std::f32::consts::TAU - std::f32::consts::FRAC_PI_2;
+            let end = (i + 1) as f32 / SEGMENTS as f32 * std::f32::consts::TAU
+                - std::f32::consts::FRAC_PI_2;
+
+            draw_line(
+                center.x + start.cos() * RADIUS,
+                center.y + start.sin() * RADIUS,
+                center.x + end.cos() * RADIUS,
+                center.y + end.sin() * RADIUS,
+                4.,
+                WHITE,
+            );
+        }
+    }
+
+    pub fn is_reloading(&self) -> bool {
+        self.reload_timer > 0.
+    }
+
+    pub fn reload(&mut self, reload_time: f32) {
+        if !self.is_reloading() && self.magazine < self.magazine_size {
+            self.reload_timer = reload_time;
+            self.reload_time = reload_time;
+        }
+    }
+
+    // Move bullets from the reserve into the magazine
+    pub fn load(&mut self, reserve: &mut usize) {
+        let amount = self
+            .magazine_size
+            .saturating_sub(self.magazine)
+            .min(*reserve);
+
+        self.magazine += amount;
+        *reserve -= amount;
+
+        self.reload_timer = 0.;
+        self.needs_load = false;
+    }
+
+    // Put the bullets in the magazine back into the reserve
+    pub fn unload(&mut self, reserve: &mut usize) {
+        *reserve += self.magazine;
+
+        self.magazine = 0;
+        self.reload_timer = 0.;
     }
 
     pub fn reset(&mut self, player_pos: Vec2) {
-        self.bullets = 3;
+        self.magazine = 0;
+        self.needs_load = true;
+        self.reload_timer = 0.;
         self.can_shoot = true;
+        self.cooldown = 0.;
 
-        // Reset the rect position
-        self.rect.x = player_pos.x;
-        self.rect.y = player_pos.y;
+        self.pivot = player_pos;
     }
 }
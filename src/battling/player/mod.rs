@@ -1,2 +1,3 @@
+pub mod ability;
 pub mod gun;
 pub mod player;
@@ -1,4 +1,5 @@
 use crate::battling::enemy::Enemy;
+use crate::objects::animation::{Animation, AnimationData};
 
 use imagesize::size;
 use macroquad::prelude::*;
@@ -13,10 +14,32 @@ const PLAYER_SIZE: Lazy<Vec2> = Lazy::new(|| {
 });
 
 pub const PLAYER_SPEED: f32 = 250.;
+pub const PLAYER_ACCELERATION: f32 = 1500.;
+pub const PLAYER_FRICTION: f32 = 1800.;
+
+// How long the player can't be hit after taking damage
+const INVULNERABLE_TIME: f32 = 1.;
+const DASH_SPEED_MULTIPLIER: f32 = 4.;
 
 pub struct Player {
     pub rect: Rect,
     pub texture: String,
+    // Top speed, and how quickly it is reached and lost
+    pub speed: f32,
+    pub acceleration: f32,
+    pub friction: f32,
+
+    pub hp: usize,
+    pub max_hp: usize,
+    pub is_invincible: bool,
+    // The player is drawn mirrored when facing left
+    pub is_facing_left: bool,
+
+    invulnerable_timer: f32,
+    vel: Vec2,
+    dash_timer: f32,
+    walk: Animation,
+    is_moving: bool,
 }
 
 impl Player {
@@ -30,55 +53,155 @@ impl Player {
                 PLAYER_SIZE[1],
             ),
             texture: "res/regular/apple.png".to_string(),
+            speed: PLAYER_SPEED,
+            acceleration: PLAYER_ACCELERATION,
+            friction: PLAYER_FRICTION,
+
+            hp: 1,
+            max_hp: 1,
+            is_invincible: false,
+            is_facing_left: false,
+
+            invulnerable_timer: 0.,
+            vel: Vec2::ZERO,
+            dash_timer: 0.,
+            walk: Animation::new(&AnimationData::load("res/animations/walk.txt").await),
+            is_moving: false,
         }
     }
 
     pub fn update(&mut self, dt: f32) {
-        match (
-            is_key_down(KeyCode::Left) || is_key_down(KeyCode::A) || is_key_down(KeyCode::H),
-            is_key_down(KeyCode::Right) || is_key_down(KeyCode::D) || is_key_down(KeyCode::L),
-        ) {
-            // Move the player to the left
-            (true, _) => {
-                self.rect.x -= dt * PLAYER_SPEED;
-            }
-            // Move the player to the right
-            (_, true) => {
-                self.rect.x += dt * PLAYER_SPEED;
-            }
-            _ => {}
+        self.invulnerable_timer = (self.invulnerable_timer - dt).max(0.);
+
+        // Dashing overrides the normal movement
+        if self.dash_timer > 0. {
+            self.dash_timer -= dt;
+        } else {
+            self.accelerate(dt);
         }
 
-        match (
-            is_key_down(KeyCode::Up) || is_key_down(KeyCode::W) || is_key_down(KeyCode::K),
-            is_key_down(KeyCode::Down) || is_key_down(KeyCode::S) || is_key_down(KeyCode::J),
-        ) {
-            // Move the player up
-            (true, _) => {
-                self.rect.y -= dt * PLAYER_SPEED;
-            }
-            // Move the player down
-            (_, true) => {
-                self.rect.y += dt * PLAYER_SPEED;
-            }
-            _ => {}
+        let pos_before = self.rect.point();
+
+        self.rect.x += dt * self.vel.x;
+        self.rect.y += dt * self.vel.y;
+
+        // Walk while moving, and start the walk from the beginning next time
+        self.is_moving = self.rect.point() != pos_before;
+
+        if self.is_moving {
+            self.walk.update(dt);
+        } else {
+            self.walk.restart();
+        }
+    }
+
+    // The keys held down as a direction, opposite keys cancel out
+    fn input() -> Vec2 {
+        let axis = |negative: bool, positive: bool| (positive as i32 - negative as i32) as f32;
+
+        vec2(
+            axis(
+                is_key_down(KeyCode::Left) || is_key_down(KeyCode::A) || is_key_down(KeyCode::H),
+                is_key_down(KeyCode::Right) || is_key_down(KeyCode::D) || is_key_down(KeyCode::L),
+            ),
+            axis(
+                is_key_down(KeyCode::Up) || is_key_down(KeyCode::W) || is_key_down(KeyCode::K),
+                is_key_down(KeyCode::Down) || is_key_down(KeyCode::S) || is_key_down(KeyCode::J),
+            ),
+        )
+        // Going diagonally isn't faster
+        .normalize_or_zero()
+    }
+
+    fn accelerate(&mut self, dt: f32) {
+        let input = Self::input();
+        // A dash can leave the player going faster than they can walk
+        let was_too_fast = self.vel.length() > self.speed;
+
+        self.vel += input * self.acceleration * dt;
+
+        if !was_too_fast {
+            self.vel = self.vel.clamp_length_max(self.speed);
+        }
+
+        // Friction slows the player down when they let go, and after a dash
+        if input == Vec2::ZERO || was_too_fast {
+            let min_speed = if input == Vec2::ZERO { 0. } else { self.speed };
+            let speed = self.vel.length();
+
+            self.vel = self.vel.normalize_or_zero()
+                * (speed - self.friction * dt).max(min_speed).min(speed);
         }
     }
 
     pub async fn draw(&self) {
-        // Draw the player
-        draw_texture(
-            load_texture(self.texture.as_str()).await.unwrap(),
-            self.rect.x,
-            self.rect.y,
-            WHITE,
-        );
+        let texture = load_texture(self.texture.as_str()).await.unwrap();
+
+        // Draw the player, flickering while invulnerable
+        let color = if self.is_invincible {
+            GOLD
+        } else if self.is_invulnerable() && (get_time() * 10.) as i32 % 2 == 0 {
+            Color::new(1., 1., 1., 0.4)
+        } else {
+            WHITE
+        };
+
+        if self.is_moving {
+            self.walk
+                .draw(texture, self.rect, color, 0., self.is_facing_left);
+        } else {
+            draw_texture_ex(
+                texture,
+                self.rect.x,
+                self.rect.y,
+                color,
+                DrawTextureParams {
+                    flip_x: self.is_facing_left,
+                    ..Default::default()
+                },
+            );
+        }
     }
 
     pub fn reset(&mut self) {
         // Reset the rect position
         self.rect.x = screen_width() / 2. - PLAYER_SIZE[0] / 2.;
-        self.rect.y = screen_height() / 2. - PLAYER_SIZE[1] / 2.
+        self.rect.y = screen_height() / 2. - PLAYER_SIZE[1] / 2.;
+
+        self.hp = self.max_hp;
+        self.invulnerable_timer = 0.;
+        self.vel = Vec2::ZERO;
+        self.dash_timer = 0.;
+    }
+
+    pub fn dash(&mut self, dir: Vec2, duration: f32) {
+        self.vel = dir.normalize_or_zero() * self.speed * DASH_SPEED_MULTIPLIER;
+        self.dash_timer = duration;
+    }
+
+    // Move out of an obstacle and stop going into it
+    pub fn push_out(&mut self, offset: Vec2) {
+        self.rect = self.rect.offset(offset);
+
+        if offset.x != 0. {
+            self.vel.x = 0.;
+        }
+
+        if offset.y != 0. {
+            self.vel.y = 0.;
+        }
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_timer > 0. || self.dash_timer > 0.
+    }
+
+    // Returns true if the hit killed the player
+    pub fn hit(&mut self) -> bool {
+        self.hp = self.hp.saturating_sub(1);
+        self.invulnerable_timer = INVULNERABLE_TIME;
+
+        self.hp == 0
     }
 
     pub fn is_collision(&self, enemy: Enemy) -> bool {
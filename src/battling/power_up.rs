@@ -0,0 +1,182 @@
+use crate::objects::layout::{self, Anchor, Stack};
+
+use macroquad::prelude::*;
+
+const POWER_UP_SIZE: f32 = 30.;
+// Seconds before an uncollected power-up disappears
+const DESPAWN_TIME: f32 = 10.;
+// Collecting a power-up that is already active adds to its time, up to this many times its duration
+const MAX_STACK: f32 = 3.;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum PowerUpKind {
+    RapidFire,
+    TripleShot,
+    SlowMotion,
+    DoubleMoney,
+    Invincibility,
+}
+
+impl PowerUpKind {
+    pub const ALL: [PowerUpKind; 5] = [
+        PowerUpKind::RapidFire,
+        PowerUpKind::TripleShot,
+        PowerUpKind::SlowMotion,
+        PowerUpKind::DoubleMoney,
+        PowerUpKind::Invincibility,
+    ];
+
+    // Letter shown on the icon
+    fn icon(&self) -> &'static str {
+        match self {
+            PowerUpKind::RapidFire => "R",
+            PowerUpKind::TripleShot => "3",
+            PowerUpKind::SlowMotion => "S",
+            PowerUpKind::DoubleMoney => "$",
+            PowerUpKind::Invincibility => "I",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            PowerUpKind::RapidFire => RED,
+            PowerUpKind::TripleShot => ORANGE,
+            PowerUpKind::SlowMotion => SKYBLUE,
+            PowerUpKind::DoubleMoney => GOLD,
+            PowerUpKind::Invincibility => VIOLET,
+        }
+    }
+
+    // Seconds the power-up lasts
+    fn duration(&self) -> f32 {
+        match self {
+            PowerUpKind::RapidFire => 8.,
+            PowerUpKind::TripleShot => 8.,
+            PowerUpKind::SlowMotion => 5.,
+            PowerUpKind::DoubleMoney => 10.,
+            PowerUpKind::Invincibility => 4.,
+        }
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|kind| kind == self).unwrap()
+    }
+}
+
+fn draw_icon(kind: PowerUpKind, pos: Vec2, size: f32, font: Font) {
+    draw_circle(pos.x, pos.y, size / 2., kind.color());
+    draw_circle_lines(pos.x, pos.y, size / 2., 2., BLACK);
+
+    draw_text_ex(
+        kind.icon(),
+        pos.x - size * 0.2,
+        pos.y + size * 0.2,
+        TextParams {
+            font,
+            font_size: (size * 0.6) as u16,
+            color: BLACK,
+            font_scale: 1.,
+            font_scale_aspect: 1.,
+        },
+    );
+}
+
+pub struct PowerUp {
+    pub rect: Rect,
+    pub kind: PowerUpKind,
+    pub is_collected: bool,
+
+    font: Font,
+    despawn_timer: f32,
+}
+
+impl PowerUp {
+    pub async fn new(pos: Vec2, kind: PowerUpKind) -> Self {
+        Self {
+            // A rect will represent the power-up bounds
+            rect: Rect::new(pos.x, pos.y, POWER_UP_SIZE, POWER_UP_SIZE),
+            kind,
+            is_collected: false,
+
+            font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
+            despawn_timer: DESPAWN_TIME,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.despawn_timer -= dt;
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.despawn_timer <= 0.
+    }
+
+    pub fn draw(&self) {
+        // Bob up and down so the power-up stands out
+        let offset = (get_time() as f32 * 4.).sin() * 3.;
+
+        draw_icon(
+            self.kind,
+            self.rect.point() + self.rect.size() * 0.5 + vec2(0., offset),
+            POWER_UP_SIZE,
+            self.font,
+        );
+    }
+}
+
+// The power-ups that are currently active
+#[derive(Default)]
+pub struct Buffs {
+    timers: [f32; PowerUpKind::ALL.len()],
+}
+
+impl Buffs {
+    pub fn add(&mut self, kind: PowerUpKind) {
+        let timer = &mut self.timers[kind.index()];
+
+        *timer = (*timer + kind.duration()).min(kind.duration() * MAX_STACK);
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        for timer in self.timers.iter_mut() {
+            *timer = (*timer - dt).max(0.);
+        }
+    }
+
+    pub fn is_active(&self, kind: PowerUpKind) -> bool {
+        self.timers[kind.index()] > 0.
+    }
+
+    pub fn reset(&mut self) {
+        self.timers = Default::default();
+    }
+
+    pub fn draw(&self, font: Font) {
+        const ICON_SIZE: f32 = 36.;
+
+        let active: Vec<PowerUpKind> = PowerUpKind::ALL
+            .into_iter()
+            .filter(|kind| self.is_active(*kind))
+            .collect();
+
+        // Line the icons up under the retreat button
+        let row = Stack::row(Anchor::TopRight, vec2(ICON_SIZE, ICON_SIZE), active.len())
+            .with_offset(vec2(0., 62.));
+
+        for (i, kind) in active.into_iter().enumerate() {
+            // The first icon goes on the right
+            let rect = row.rect(row.count - 1 - i);
+
+            draw_icon(kind, rect.point() + rect.size() * 0.5, rect.w, font);
+
+            // Draw the time left under the icon
+            layout::draw_text_centered(
+                format!("{:.0}s", self.timers[kind.index()].ceil()).as_str(),
+                Rect::new(rect.x, rect.y + rect.h, rect.w, rect.h * 0.5),
+                font,
+                layout::font_size(15),
+                BLACK,
+            );
+        }
+    }
+}
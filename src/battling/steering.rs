@@ -0,0 +1,156 @@
+use macroquad::prelude::*;
+
+// Enemies closer than this push away from each other
+const SEPARATION_RADIUS: f32 = 70.;
+// Enemies closer than this move as a group
+const FLOCK_RADIUS: f32 = 150.;
+// Pixels ahead an enemy looks for obstacles
+const LOOK_AHEAD: f32 = 80.;
+
+// How much each behaviour counts towards where an enemy wants to go
+const SEEK_WEIGHT: f32 = 1.;
+const SEPARATION_WEIGHT: f32 = 1.5;
+const ALIGNMENT_WEIGHT: f32 = 0.3;
+const COHESION_WEIGHT: f32 = 0.2;
+const AVOIDANCE_WEIGHT: f32 = 2.;
+
+// Where an enemy is and where it is going, for the others to steer around
+#[derive(Clone, Copy)]
+pub struct Boid {
+    pub pos: Vec2,
+    pub vel: Vec2,
+}
+
+// Each behaviour returns the velocity the enemy would like to have
+
+pub fn seek(pos: Vec2, target: Vec2, max_speed: f32) -> Vec2 {
+    (target - pos).normalize_or_zero() * max_speed
+}
+
+pub fn flee(pos: Vec2, threat: Vec2, max_speed: f32) -> Vec2 {
+    -seek(pos, threat, max_speed)
+}
+
+// Move away from enemies that are too close, harder the closer they are
+pub fn separation(pos: Vec2, neighbours: &[Boid], max_speed: f32) -> Vec2 {
+    let mut push = Vec2::ZERO;
+
+    for neighbour in neighbours {
+        let away = pos - neighbour.pos;
+        let distance = away.length();
+
+        if distance > 0. && distance < SEPARATION_RADIUS {
+            push += away / distance * (1. - distance / SEPARATION_RADIUS);
+        }
+    }
+
+    push.clamp_length_max(1.) * max_speed
+}
+
+fn flock(pos: Vec2, neighbours: &[Boid]) -> impl Iterator<Item = &Boid> {
+    neighbours.iter().filter(move |neighbour| {
+        let distance = neighbour.pos.distance(pos);
+
+        distance > 0. && distance < FLOCK_RADIUS
+    })
+}
+
+// Go the same way as the enemies nearby
+pub fn alignment(pos: Vec2, neighbours: &[Boid], max_speed: f32) -> Vec2 {
+    let heading = flock(pos, neighbours).fold(Vec2::ZERO, |sum, neighbour| sum + neighbour.vel);
+
+    heading.normalize_or_zero() * max_speed
+}
+
+// Go towards the middle of the enemies nearby
+pub fn cohesion(pos: Vec2, neighbours: &[Boid], max_speed: f32) -> Vec2 {
+    let (sum, count) = flock(pos, neighbours).fold((Vec2::ZERO, 0), |(sum, count), neighbour| {
+        (sum + neighbour.pos, count + 1)
+    });
+
+    if count == 0 {
+        return Vec2::ZERO;
+    }
+
+    seek(pos, sum / count as f32, max_speed)
+}
+
+// Turn away from an obstacle that is in the way
+pub fn avoid_obstacles(pos: Vec2, vel: Vec2, obstacles: &[Rect], max_speed: f32) -> Vec2 {
+    let dir = vel.normalize_or_zero();
+
+    // Check a point halfway and a point all the way to the look ahead distance
+    for ahead in [pos + dir * LOOK_AHEAD * 0.5, pos + dir * LOOK_AHEAD] {
+        if let Some(obstacle) = obstacles.iter().find(|obstacle| obstacle.contains(ahead)) {
+            let center = obstacle.point() + obstacle.size() * 0.5;
+
+            return (ahead - center).normalize_or_zero() * max_speed;
+        }
+    }
+
+    Vec2::ZERO
+}
+
+// Every behaviour added together, towards the target or away from it
+pub fn steer(
+    boid: Boid,
+    target: Vec2,
+    is_fleeing: bool,
+    neighbours: &[Boid],
+    obstacles: &[Rect],
+    max_speed: f32,
+) -> Vec2 {
+    let goal = if is_fleeing {
+        flee(boid.pos, target, max_speed)
+    } else {
+        seek(boid.pos, target, max_speed)
+    };
+
+    let desired = goal * SEEK_WEIGHT
+        + separation(boid.pos, neighbours, max_speed) * SEPARATION_WEIGHT
+        + alignment(boid.pos, neighbours, max_speed) * ALIGNMENT_WEIGHT
+        + cohesion(boid.pos, neighbours, max_speed) * COHESION_WEIGHT
+        + avoid_obstacles(boid.pos, boid.vel, obstacles, max_speed) * AVOIDANCE_WEIGHT;
+
+    desired.clamp_length_max(max_speed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_finite(vec: Vec2) -> bool {
+        vec.x.is_finite() && vec.y.is_finite()
+    }
+
+    #[test]
+    fn neighbour_in_the_same_place_gives_no_nan() {
+        let boid = Boid {
+            pos: vec2(100., 100.),
+            vel: Vec2::ZERO,
+        };
+        let neighbours = [boid, boid];
+
+        assert!(is_finite(separation(boid.pos, &neighbours, 250.)));
+        assert!(is_finite(alignment(boid.pos, &neighbours, 250.)));
+        assert!(is_finite(cohesion(boid.pos, &neighbours, 250.)));
+        assert!(is_finite(steer(
+            boid,
+            boid.pos,
+            false,
+            &neighbours,
+            &[],
+            250.
+        )));
+    }
+
+    #[test]
+    fn separation_pushes_away_from_close_neighbours() {
+        let neighbour = Boid {
+            pos: vec2(10., 0.),
+            vel: Vec2::ZERO,
+        };
+
+        assert!(separation(Vec2::ZERO, &[neighbour], 250.).x < 0.);
+    }
+}
@@ -3,18 +3,52 @@ mod objects;
 mod states;
 mod upgrading;
 
+use objects::achievements::Achievements;
+use objects::settings::Settings;
+use objects::stats::{StatEvent, Stats};
+use states::achievement_gallery::AchievementGallery;
 use states::battling::Battling;
 use states::dead::Dead;
 use states::game_state::GameState;
+use states::high_scores::HighScores;
 use states::menu::Menu;
+use states::options::Options;
+use states::statistics::Statistics;
 use states::tutorial::Tutorial;
 use states::upgrading::Upgrading;
+use states::victory::Victory;
+use upgrading::character::Character;
+use upgrading::tier;
+use upgrading::upgrades::Upgrades;
 
-use macroquad::audio::{load_sound, play_sound, stop_sound, PlaySoundParams};
+use macroquad::audio::{load_sound, play_sound, set_sound_volume, stop_sound, PlaySoundParams};
 use macroquad::prelude::*;
 
 const MUSIC_TIME: f32 = 13.;
 
+// Start over as an apple with nothing
+async fn reset_progress(battling: &mut Battling, upgrading: &mut Upgrading) {
+    upgrading.character = Character::new().await;
+    upgrading.upgrades = Upgrades::default();
+    upgrading.bosses_defeated = 0;
+    upgrading.money.reset();
+    upgrading.bullets.reset();
+
+    battling.bosses_defeated = 0;
+    battling.new_game_plus = 0;
+    battling.is_endless = false;
+    battling.money.reset();
+    battling.bullet_count.reset();
+
+    resume_run(battling, upgrading);
+}
+
+// Battling and upgrading are left on the state they exited to, so put them back before playing
+fn resume_run(battling: &mut Battling, upgrading: &mut Upgrading) {
+    battling.game_state = GameState::Battling;
+    upgrading.game_state = GameState::Upgrading;
+}
+
 #[macroquad::main("Becoming The Banana Man")]
 async fn main() {
     let mut game_state = GameState::Menu;
@@ -24,6 +58,18 @@ async fn main() {
     let mut menu = Menu::new().await;
     let mut dead = Dead::new().await;
     let mut tutorial = Tutorial::new().await;
+    let mut victory = Victory::new().await;
+    let mut statistics = Statistics::new().await;
+    let mut high_scores = HighScores::new().await;
+    let mut achievement_gallery = AchievementGallery::new().await;
+    let mut options = Options::new().await;
+
+    // Stats for every session, and for the current run
+    let mut stats = Stats::load();
+    let mut run_stats = Stats::default();
+    let mut achievements = Achievements::load();
+    let mut settings = Settings::load();
+    let mut last_game_state = game_state;
 
     let mut music_timer = 0.;
 
@@ -34,19 +80,32 @@ async fn main() {
 
         println!("{:?}", music_timer);
 
+        if game_state == GameState::Battling || game_state == GameState::Upgrading {
+            let tier = tier::index(&upgrading.character.character);
+
+            stats.add_time(get_frame_time(), tier);
+            run_stats.add_time(get_frame_time(), tier);
+        }
+
         match game_state {
             GameState::Battling => match battling.game_state {
                 GameState::Battling => {
                     // Communicate data between upgrading and battling
                     upgrading.money = battling.money.clone();
                     upgrading.bullets = battling.bullet_count.clone();
+                    upgrading.bosses_defeated = battling.bosses_defeated;
                     battling.character = upgrading.character.clone();
+                    battling.upgrades = upgrading.upgrades.clone();
+                    battling.particles.density = settings.particle_density;
+                    battling.camera.shake_strength = settings.screen_shake;
+                    battling.camera.is_hit_stop_on = settings.is_hit_stop_on;
+                    battling.camera.is_zoom_punch_on = settings.is_zoom_punch_on;
 
                     if music_timer <= 0. {
                         play_sound(
                             music,
                             PlaySoundParams {
-                                volume: 0.5,
+                                volume: settings.music_volume(),
                                 looped: false,
                             },
                         );
@@ -62,6 +121,23 @@ async fn main() {
                 }
 
                 _ => {
+                    // Communicate data changed on the last battling frame
+                    upgrading.money = battling.money.clone();
+                    upgrading.bullets = battling.bullet_count.clone();
+                    upgrading.bosses_defeated = battling.bosses_defeated;
+
+                    if battling.game_state == GameState::Dead {
+                        dead.run = battling.last_run.clone();
+                        dead.is_new_run = true;
+
+                        // Hardcore deaths lose everything
+                        if battling.difficulty.is_permadeath() {
+                            reset_progress(&mut battling, &mut upgrading).await;
+
+                            run_stats = Stats::default();
+                        }
+                    }
+
                     // Communicate game states
                     game_state = battling.game_state;
                     upgrading.game_state = battling.game_state;
@@ -79,7 +155,7 @@ async fn main() {
                         play_sound(
                             music,
                             PlaySoundParams {
-                                volume: 0.5,
+                                volume: settings.music_volume(),
                                 looped: false,
                             },
                         );
@@ -93,8 +169,13 @@ async fn main() {
                 }
 
                 _ => {
+                    // Communicate if the upgrade button started a boss fight
+                    battling.is_boss_fight = upgrading.start_boss_fight;
+                    upgrading.start_boss_fight = false;
+
                     // Communicate game states
                     battling.game_state = upgrading.game_state;
+                    victory.game_state = upgrading.game_state;
                     game_state = upgrading.game_state;
                 }
             },
@@ -102,8 +183,26 @@ async fn main() {
             GameState::Menu => {
                 // Communicate game state
                 tutorial.game_state = menu.game_state;
+                statistics.game_state = menu.game_state;
+                high_scores.game_state = menu.game_state;
+                achievement_gallery.game_state = menu.game_state;
+                options.game_state = menu.game_state;
                 game_state = menu.game_state;
 
+                if menu.start_new_game {
+                    reset_progress(&mut battling, &mut upgrading).await;
+
+                    battling.difficulty = menu.difficulty;
+                    run_stats = Stats::default();
+                    achievements.record(StatEvent::NewGame);
+
+                    menu.start_new_game = false;
+                    menu.can_continue = true;
+                } else if menu.game_state == GameState::Battling {
+                    // Continue the run that was left for the menu
+                    resume_run(&mut battling, &mut upgrading);
+                }
+
                 stop_sound(music);
 
                 music_timer = 0.;
@@ -119,7 +218,8 @@ async fn main() {
                 battling.game_state = dead.game_state;
 
                 // Communicate data
-                dead.out_of_bullets = battling.out_of_bullets;
+                dead.report = battling.death_report.clone();
+                battling.next_seed = dead.retry_seed.take();
 
                 stop_sound(music);
 
@@ -128,6 +228,94 @@ async fn main() {
                 dead.start().await;
             }
 
+            GameState::Victory => {
+                // Communicate game states
+                menu.game_state = GameState::Menu;
+
+                game_state = victory.game_state;
+                upgrading.game_state = victory.game_state;
+                battling.game_state = victory.game_state;
+
+                // Communicate data
+                victory.stats = run_stats.clone();
+
+                if victory.is_endless {
+                    battling.is_endless = true;
+                    victory.is_endless = false;
+                }
+
+                if victory.start_new_game_plus {
+                    // Start over as an apple, keeping the stat upgrades
+                    battling.new_game_plus += 1;
+                    battling.is_endless = false;
+                    battling.bosses_defeated = 0;
+                    upgrading.bosses_defeated = 0;
+                    upgrading.character = Character::new().await;
+
+                    battling.money.reset();
+                    battling.bullet_count.reset();
+                    upgrading.money.reset();
+                    upgrading.bullets.reset();
+
+                    run_stats = Stats::default();
+                    achievements.record(StatEvent::NewGame);
+
+                    victory.start_new_game_plus = false;
+                }
+
+                stop_sound(music);
+
+                music_timer = 0.;
+
+                victory.start().await;
+            }
+
+            GameState::Statistics => {
+                // Communicate game states
+                game_state = statistics.game_state;
+                menu.game_state = statistics.game_state;
+
+                // Communicate data
+                statistics.stats = stats.clone();
+
+                statistics.start().await;
+            }
+
+            GameState::HighScores => {
+                // Communicate game states
+                game_state = high_scores.game_state;
+                menu.game_state = high_scores.game_state;
+
+                // Communicate data
+                high_scores.leaderboard = dead.leaderboard.clone();
+
+                high_scores.start().await;
+            }
+
+            GameState::Achievements => {
+                // Communicate game states
+                game_state = achievement_gallery.game_state;
+                menu.game_state = achievement_gallery.game_state;
+
+                // Communicate data
+                achievement_gallery.achievements = achievements.clone();
+
+                achievement_gallery.start().await;
+            }
+
+            GameState::Options => {
+                // Communicate game states
+                game_state = options.game_state;
+                menu.game_state = options.game_state;
+
+                options.settings = settings.clone();
+
+                options.start().await;
+
+                // Communicate changed settings
+                settings = options.settings.clone();
+            }
+
             GameState::Tutorial => {
                 // Communicate game states
                 game_state = tutorial.game_state;
@@ -137,6 +325,30 @@ async fn main() {
             }
         }
 
+        // The music volume can be changed while it plays
+        set_sound_volume(music, settings.music_volume());
+
+        // Record the things that happened this frame
+        for event in battling
+            .stat_events
+            .drain(..)
+            .chain(upgrading.stat_events.drain(..))
+            .chain(dead.stat_events.drain(..))
+        {
+            stats.record(event);
+            run_stats.record(event);
+            achievements.record(event);
+        }
+
+        achievements.draw_toasts().await;
+
+        // Save the stats whenever the game state changes
+        if game_state != last_game_state {
+            stats.save();
+
+            last_game_state = game_state;
+        }
+
         next_frame().await
     }
 }
@@ -0,0 +1,242 @@
+use crate::objects::layout::{self, Anchor};
+use crate::objects::save_file;
+use crate::objects::stats::StatEvent;
+use crate::upgrading::tier::{self, TIERS};
+
+use macroquad::prelude::*;
+
+const ACHIEVEMENTS_FILE: &str = "achievements.txt";
+// Seconds a toast stays on screen
+const TOAST_TIME: f32 = 4.;
+const STREAK_KILLS: usize = 100;
+const BULK_BULLETS: usize = 100;
+
+pub struct Achievement {
+    // Saved to the file, so it must never change
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 8] = [
+    Achievement {
+        id: "first_kill",
+        name: "First Blood",
+        description: "Kill your first enemy",
+    },
+    Achievement {
+        id: "kill_streak",
+        name: "No Way Back",
+        description: "Get 100 kills without retreating or dying",
+    },
+    Achievement {
+        id: "clean_pineapple",
+        name: "Spotless Pineapple",
+        description: "Become a pineapple without dying",
+    },
+    Achievement {
+        id: "bulk_buy",
+        name: "Stocking Up",
+        description: "Buy 100 bullets at once",
+    },
+    Achievement {
+        id: "first_boss",
+        name: "Royal Pain",
+        description: "Defeat a boss",
+    },
+    Achievement {
+        id: "banana_man",
+        name: "The Banana Man",
+        description: "Become the banana",
+    },
+    Achievement {
+        id: "high_score",
+        name: "Hall of Fame",
+        description: "Get on the leaderboard",
+    },
+    Achievement {
+        id: "hardcore_death",
+        name: "Back to Square Apple",
+        description: "Die on Hardcore",
+    },
+];
+
+#[derive(Clone)]
+struct Toast {
+    achievement: usize,
+    timer: f32,
+}
+
+#[derive(Clone, Default)]
+pub struct Achievements {
+    pub unlocked: [bool; ACHIEVEMENTS.len()],
+
+    // Progress towards achievements that take more than one event
+    kills_without_retreat: usize,
+    has_died: bool,
+
+    toasts: Vec<Toast>,
+    // Loaded the first time a toast is drawn
+    font: Option<Font>,
+}
+
+impl Achievements {
+    pub fn record(&mut self, event: StatEvent) {
+        match event {
+            StatEvent::Kill => {
+                self.unlock("first_kill");
+
+                self.kills_without_retreat += 1;
+
+                if self.kills_without_retreat >= STREAK_KILLS {
+                    self.unlock("kill_streak");
+                }
+            }
+            StatEvent::Retreat => self.kills_without_retreat = 0,
+            StatEvent::Death => {
+                self.kills_without_retreat = 0;
+                self.has_died = true;
+            }
+            StatEvent::HardcoreDeath => self.unlock("hardcore_death"),
+            StatEvent::NewGame => {
+                self.kills_without_retreat = 0;
+                self.has_died = false;
+            }
+            StatEvent::TierReached(index) => {
+                if index >= tier::index("Pineapple") && !self.has_died {
+                    self.unlock("clean_pineapple");
+                }
+
+                if index == TIERS.len() - 1 {
+                    self.unlock("banana_man");
+                }
+            }
+            StatEvent::BulletsBought(amount) if amount >= BULK_BULLETS => self.unlock("bulk_buy"),
+            StatEvent::BossDefeated => self.unlock("first_boss"),
+            StatEvent::HighScore => self.unlock("high_score"),
+            _ => {}
+        }
+    }
+
+    fn unlock(&mut self, id: &str) {
+        let Some(i) = ACHIEVEMENTS
+            .iter()
+            .position(|achievement| achievement.id == id)
+        else {
+            return;
+        };
+
+        if self.unlocked[i] {
+            return;
+        }
+
+        self.unlocked[i] = true;
+        self.toasts.push(Toast {
+            achievement: i,
+            timer: TOAST_TIME,
+        });
+
+        self.save();
+    }
+
+    pub fn unlocked_count(&self) -> usize {
+        self.unlocked.iter().filter(|unlocked| **unlocked).count()
+    }
+
+    pub fn load() -> Self {
+        let mut achievements = Self::default();
+
+        // Nothing is unlocked if nothing has been saved yet
+        let Some(file) = save_file::load(ACHIEVEMENTS_FILE) else {
+            return achievements;
+        };
+
+        // The file is the ids of the unlocked achievements
+        for line in file.lines() {
+            if let Some(i) = ACHIEVEMENTS
+                .iter()
+                .position(|achievement| achievement.id == line.trim())
+            {
+                achievements.unlocked[i] = true;
+            }
+        }
+
+        achievements
+    }
+
+    pub fn save(&self) {
+        let mut file = String::new();
+
+        for (achievement, unlocked) in ACHIEVEMENTS.iter().zip(self.unlocked) {
+            if unlocked {
+                file += format!("{}\n", achievement.id).as_str();
+            }
+        }
+
+        save_file::save(ACHIEVEMENTS_FILE, &file);
+    }
+
+    pub async fn draw_toasts(&mut self) {
+        for toast in &mut self.toasts {
+            toast.timer -= get_frame_time();
+        }
+
+        self.toasts.retain(|toast| toast.timer > 0.);
+
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        let font = match self.font {
+            Some(font) => font,
+            None => *self
+                .font
+                .insert(load_ttf_font("res/Roboto-Medium.ttf").await.unwrap()),
+        };
+
+        // Stack the toasts up from the bottom right corner
+        for (i, toast) in self.toasts.iter().enumerate() {
+            let achievement = &ACHIEVEMENTS[toast.achievement];
+            let rect = layout::place(
+                Anchor::BottomRight,
+                vec2(300., 60.),
+                vec2(0., i as f32 * -70.),
+            );
+            let (pos, size) = (rect.point(), rect.size());
+
+            // Fade out during the last second
+            let alpha = toast.timer.min(1.);
+
+            draw_rectangle(pos.x, pos.y, size.x, size.y, Color::new(1., 0.8, 0., alpha));
+            draw_rectangle_lines(
+                pos.x,
+                pos.y,
+                size.x,
+                size.y,
+                2.,
+                Color::new(0., 0., 0., alpha),
+            );
+
+            let params = |font_size: u16| TextParams {
+                font,
+                font_size: layout::font_size(font_size),
+                color: Color::new(0., 0., 0., alpha),
+                font_scale: 1.,
+                font_scale_aspect: 1.,
+            };
+
+            draw_text_ex(
+                "Achievement unlocked!",
+                pos.x + size.x * 0.03,
+                pos.y + size.y * 0.37,
+                params(16),
+            );
+            draw_text_ex(
+                achievement.name,
+                pos.x + size.x * 0.03,
+                pos.y + size.y * 0.8,
+                params(22),
+            );
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use crate::objects::data_file::{self, parse_vec2, ParseError};
+
+use macroquad::file::load_string;
+use macroquad::prelude::*;
+
+// What happens when the last frame is done
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mode {
+    Loop,
+    Once,
+}
+
+#[derive(Clone)]
+pub struct Frame {
+    // Which cell of the sprite sheet to show, counted left to right then top to bottom
+    // Always 0 for animations without a sprite sheet, which only move the whole texture
+    pub cell: usize,
+    // Seconds the frame is shown for
+    pub duration: f32,
+    pub scale: Vec2,
+    // Pixels the frame is moved by
+    pub offset: Vec2,
+    // Degrees the frame is turned by
+    pub rotation: f32,
+    pub alpha: f32,
+    // Sent when the frame starts
+    pub event: Option<String>,
+}
+
+// An animation loaded from a file in res/animations
+#[derive(Clone)]
+pub struct AnimationData {
+    // Size of one cell of the sprite sheet, the whole texture if there is none
+    pub frame_size: Option<Vec2>,
+    pub mode: Mode,
+    pub frames: Vec<Frame>,
+}
+
+// A frame line is the cell and duration followed by optional key=value pairs
+fn parse_frame(text: &str) -> Result<Frame, String> {
+    let mut parts = text.split_whitespace();
+
+    let mut frame = Frame {
+        cell: parts
+            .next()
+            .and_then(|cell| cell.parse().ok())
+            .ok_or("expected a cell number")?,
+        duration: parts
+            .next()
+            .and_then(|duration| duration.parse().ok())
+            .ok_or("expected a duration in seconds")?,
+        scale: Vec2::ONE,
+        offset: Vec2::ZERO,
+        rotation: 0.,
+        alpha: 1.,
+        event: None,
+    };
+
+    for part in parts {
+        let Some((key, value)) = part.split_once('=') else {
+            return Err(format!("expected key=value, found \"{}\"", part));
+        };
+
+        let is_valid = match key {
+            "scale" => parse_vec2(value).map(|scale| frame.scale = scale).is_some(),
+            "offset" => parse_vec2(value)
+                .map(|offset| frame.offset = offset)
+                .is_some(),
+            "rotation" => value
+                .parse()
+                .map(|rotation| frame.rotation = rotation)
+                .is_ok(),
+            "alpha" => value.parse().map(|alpha| frame.alpha = alpha).is_ok(),
+            "event" => {
+                frame.event = Some(value.to_string());
+
+                true
+            }
+            _ => return Err(format!("unknown frame key \"{}\"", key)),
+        };
+
+        if !is_valid {
+            return Err(format!("bad value for {}: \"{}\"", key, value));
+        }
+    }
+
+    Ok(frame)
+}
+
+impl AnimationData {
+    pub async fn load(path: &str) -> Self {
+        let (data, errors) = Self::parse(load_string(path).await.unwrap().as_str());
+        data_file::report(path, &errors);
+
+        data
+    }
+
+    pub fn parse(file: &str) -> (Self, Vec<ParseError>) {
+        let mut data = Self {
+            frame_size: None,
+            mode: Mode::Loop,
+            frames: Vec::new(),
+        };
+
+        let (entries, mut errors) = data_file::parse(file);
+
+        for entry in entries {
+            match entry.key {
+                "frame_size" => match parse_vec2(entry.value) {
+                    Some(frame_size) => data.frame_size = Some(frame_size),
+                    None => errors.push(entry.error("expected width,height")),
+                },
+                "mode" => match entry.value {
+                    "loop" => data.mode = Mode::Loop,
+                    "once" => data.mode = Mode::Once,
+                    _ => errors.push(entry.error("expected loop or once")),
+                },
+                "frame" => match parse_frame(entry.value) {
+                    Ok(frame) => data.frames.push(frame),
+                    Err(message) => errors.push(entry.error(message)),
+                },
+                _ => errors.push(entry.error("unknown key")),
+            }
+        }
+
+        (data, errors)
+    }
+}
+
+#[derive(Clone)]
+pub struct Animation {
+    pub data: AnimationData,
+    // Only set once a Once animation has shown its last frame
+    pub is_finished: bool,
+    // Events of the frames that started this update
+    pub events: Vec<String>,
+
+    frame: usize,
+    timer: f32,
+    is_started: bool,
+}
+
+impl Animation {
+    pub fn new(data: &AnimationData) -> Self {
+        Self {
+            data: data.clone(),
+            is_finished: false,
+            events: Vec::new(),
+
+            frame: 0,
+            timer: 0.,
+            is_started: false,
+        }
+    }
+
+    // An animation that waits for restart before it plays
+    pub fn stopped(data: &AnimationData) -> Self {
+        let mut animation = Self::new(data);
+        animation.is_finished = true;
+
+        animation
+    }
+
+    pub fn restart(&mut self) {
+        self.is_finished = false;
+        self.frame = 0;
+        self.timer = 0.;
+        self.is_started = false;
+    }
+
+    pub fn has_event(&self, event: &str) -> bool {
+        self.events.iter().any(|e| e == event)
+    }
+
+    fn start_frame(&mut self, frame: usize) {
+        self.frame = frame;
+
+        if let Some(event) = &self.data.frames[frame].event {
+            self.events.push(event.clone());
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.events.clear();
+
+        if self.is_finished || self.data.frames.is_empty() {
+            return;
+        }
+
+        if !self.is_started {
+            self.is_started = true;
+            self.start_frame(0);
+        }
+
+        self.timer += dt;
+
+        // A long frame time can skip over short frames
+        while self.timer >= self.data.frames[self.frame].duration.max(0.001) {
+            self.timer -= self.data.frames[self.frame].duration.max(0.001);
+
+            if self.frame + 1 < self.data.frames.len() {
+                self.start_frame(self.frame + 1);
+            } else if self.data.mode == Mode::Loop {
+                self.start_frame(0);
+            } else {
+                self.is_finished = true;
+
+                return;
+            }
+        }
+    }
+
+    // Draw the current frame over a rect, the frame is scaled and turned around the centre
+    pub fn draw(&self, texture: Texture2D, rect: Rect, color: Color, rotation: f32, flip_x: bool) {
+        let Some(frame) = self.data.frames.get(self.frame) else {
+            return;
+        };
+
+        if self.is_finished {
+            return;
+        }
+
+        let frame_size = self
+            .data
+            .frame_size
+            .unwrap_or(vec2(texture.width(), texture.height()));
+        let columns = ((texture.width() / frame_size.x) as usize).max(1);
+        let source = Rect::new(
+            (frame.cell % columns) as f32 * frame_size.x,
+            (frame.cell / columns) as f32 * frame_size.y,
+            frame_size.x,
+            frame_size.y,
+        );
+
+        let size = rect.size() * frame.scale;
+        let pos = rect.point() + rect.size() * 0.5 - size * 0.5 + frame.offset;
+
+        draw_texture_ex(
+            texture,
+            pos.x,
+            pos.y,
+            Color::new(color.r, color.g, color.b, color.a * frame.alpha),
+            DrawTextureParams {
+                dest_size: Some(size),
+                source: Some(source),
+                rotation: rotation + frame.rotation.to_radians(),
+                flip_x,
+                ..Default::default()
+            },
+        );
+    }
+}
+
+// An animation played once in one place, like an enemy dying
+pub struct Effect {
+    pub rect: Rect,
+    pub texture: Texture2D,
+    pub animation: Animation,
+}
+
+impl Effect {
+    pub fn new(rect: Rect, texture: Texture2D, data: &AnimationData) -> Self {
+        Self {
+            rect,
+            texture,
+            animation: Animation::new(data),
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.animation.update(dt);
+    }
+
+    pub fn draw(&self) {
+        self.animation
+            .draw(self.texture, self.rect, WHITE, 0., false);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::objects::layout::Anchor;
 use crate::objects::text::Text;
 use macroquad::prelude::*;
 
@@ -12,7 +13,8 @@ impl Bullets {
         Self {
             bullets: 3,
             text: Text::new(
-                vec2(20., 40.),
+                Anchor::TopLeft,
+                vec2(0., 0.),
                 "res/Roboto-Medium.ttf".to_string(),
                 format!("Bullets: {}", 3.to_string()),
                 30,
@@ -26,13 +28,6 @@ impl Bullets {
         self.bullets = 3;
     }
 
-    pub fn decrement(&mut self) {
-        self.text.pos = vec2(20., 40.);
-
-        // Decrement the bullets
-        self.bullets -= 1;
-    }
-
     pub fn draw(&mut self) {
         self.text
             .change(format!("Bullets: {}", self.bullets.to_string()));
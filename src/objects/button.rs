@@ -1,5 +1,9 @@
+use crate::objects::click::Click;
+use crate::objects::layout;
+use crate::objects::tooltip;
+
 use macroquad::{
-    audio::{load_sound, play_sound, PlaySoundParams},
+    audio::{load_sound, play_sound, PlaySoundParams, Sound},
     prelude::*,
 };
 
@@ -10,7 +14,30 @@ pub struct Button {
     pub bg: Color,
     font_size: u16,
 
-    can_click: bool,
+    // Disabled buttons are greyed out and can't be clicked
+    pub is_disabled: bool,
+    pub is_focused: bool,
+    // Shown when the mouse is over the button
+    pub tooltip: Option<String>,
+
+    click: Click,
+    click_sound: Sound,
+    deny_sound: Sound,
+    // Counts down while the button shakes after a rejected click
+    shake_timer: f32,
+}
+
+// Seconds a rejected button shakes for
+const SHAKE_TIME: f32 = 0.3;
+
+// Blend a color towards another one
+fn mix(color: Color, other: Color, amount: f32) -> Color {
+    Color::new(
+        color.r + (other.r - color.r) * amount,
+        color.g + (other.g - color.g) * amount,
+        color.b + (other.b - color.b) * amount,
+        color.a,
+    )
 }
 
 impl Button {
@@ -21,60 +48,112 @@ impl Button {
             font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
             bg,
             font_size,
-            can_click: false,
+
+            is_disabled: false,
+            is_focused: false,
+            tooltip: None,
+
+            click: Click::default(),
+            click_sound: load_sound("res/audio/click.wav").await.unwrap(),
+            deny_sound: load_sound("res/audio/deny.wav").await.unwrap(),
+            shake_timer: 0.,
         }
     }
 
-    pub fn is_pressed(&mut self) -> bool {
-        if is_mouse_button_down(MouseButton::Left)
-            && self
-                .rect
-                .contains(vec2(mouse_position().0, mouse_position().1))
-        {
-            return true;
-        }
+    pub fn is_hovered(&self) -> bool {
+        self.rect.contains(mouse_position().into())
+    }
+
+    // Only true for the frame the button was clicked
+    pub fn is_clicked(&self) -> bool {
+        self.click.is_clicked
+    }
 
-        false
+    // Only true for the frame the button was clicked while disabled
+    pub fn is_rejected(&self) -> bool {
+        self.click.is_rejected
     }
 
     pub async fn draw(&mut self) {
+        // Show if the button is disabled, held down or under the mouse
+        let bg = if self.is_disabled {
+            mix(self.bg, GRAY, 0.7)
+        } else if self.click.is_held {
+            mix(self.bg, BLACK, 0.25)
+        } else if self.is_hovered() {
+            mix(self.bg, WHITE, 0.25)
+        } else {
+            self.bg
+        };
+
+        // Shake side to side after a rejected click
+        self.shake_timer = (self.shake_timer - get_frame_time()).max(0.);
+
+        let mut rect = self.rect;
+        rect.x += (self.shake_timer * 60.).sin() * self.shake_timer * 20. * layout::scale();
+
         // Draw button background
-        draw_rectangle(self.rect.x, self.rect.y, self.rect.w, self.rect.h, self.bg);
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, bg);
+
+        if self.is_focused {
+            draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 4., BLACK);
+        }
 
         // Draw button text
-        draw_text_ex(
+        layout::draw_text_centered(
             self.text.as_str(),
-            self.rect.x + self.rect.w * 0.5 - (self.text.chars().count() - 1) as f32 * 7.5 * 0.5,
-            self.rect.y + self.rect.h * 0.5 + 7.5,
-            TextParams {
-                font: self.font,
-                font_size: self.font_size,
-                color: BLACK,
-                font_scale: 1.,
-                font_scale_aspect: 1.,
-            },
+            rect,
+            self.font,
+            layout::font_size(self.font_size),
+            if self.is_disabled { DARKGRAY } else { BLACK },
         );
+    }
+
+    // Drawn separately so it can go on top of everything else
+    pub fn draw_tooltip(&self) {
+        if let Some(tooltip) = &self.tooltip {
+            if self.is_hovered() {
+                tooltip::draw(tooltip.as_str(), self.font);
+            }
+        }
+    }
 
-        // Play click sound if pressed
-        if self.is_pressed() && self.can_click {
+    // Must be called once every frame for clicks to be detected
+    pub fn update(&mut self, pos: Vec2, size: Vec2) {
+        self.rect.x = pos.x;
+        self.rect.y = pos.y;
+        self.rect.w = size[0];
+        self.rect.h = size[1];
+
+        self.click
+            .update(self.rect, self.is_focused, self.is_disabled);
+
+        // Play click sound
+        if self.click.is_clicked {
             play_sound(
-                load_sound("res/audio/click.wav").await.unwrap(),
+                self.click_sound,
                 PlaySoundParams {
                     volume: 0.5,
                     looped: false,
                 },
             );
+        }
 
-            self.can_click = false;
-        } else if !self.is_pressed() {
-            self.can_click = true;
+        // Play deny sound
+        if self.click.is_rejected {
+            self.shake_timer = SHAKE_TIME;
+
+            play_sound(
+                self.deny_sound,
+                PlaySoundParams {
+                    volume: 0.5,
+                    looped: false,
+                },
+            );
         }
     }
 
-    pub fn update(&mut self, pos: Vec2, size: Vec2) {
-        self.rect.x = pos.x;
-        self.rect.y = pos.y;
-        self.rect.w = size[0];
-        self.rect.h = size[1];
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.update(rect.point(), rect.size());
     }
 }
@@ -0,0 +1,111 @@
+use crate::objects::click::Click;
+use crate::objects::layout::{self, Anchor};
+
+use macroquad::prelude::*;
+
+pub struct Checkbox {
+    pub rect: Rect,
+    pub label: String,
+    pub is_checked: bool,
+    pub is_focused: bool,
+
+    font: Font,
+    click: Click,
+}
+
+impl Checkbox {
+    pub async fn new(label: String, is_checked: bool) -> Self {
+        Self {
+            rect: Rect::new(0., 0., 0., 0.),
+            label,
+            is_checked,
+            is_focused: false,
+
+            font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
+            click: Click::default(),
+        }
+    }
+
+    // Must be called once every frame
+    pub fn update(&mut self, rect: Rect) {
+        self.rect = rect;
+
+        self.click.update(self.rect, self.is_focused, false);
+
+        if self.click.is_clicked {
+            self.is_checked = !self.is_checked;
+        }
+    }
+
+    pub fn draw(&self) {
+        // The box is a square on the right
+        let size = self.rect.h * 0.6;
+        let check_box = Rect::new(
+            self.rect.right() - self.rect.w * 0.25 - size * 0.5,
+            self.rect.y + (self.rect.h - size) * 0.5,
+            size,
+            size,
+        );
+
+        if self.is_focused {
+            draw_rectangle_lines(
+                self.rect.x,
+                self.rect.y,
+                self.rect.w,
+                self.rect.h,
+                4.,
+                BLACK,
+            );
+        }
+
+        layout::draw_text_in(
+            self.label.as_str(),
+            Rect::new(
+                self.rect.x + 10.,
+                self.rect.y,
+                self.rect.w * 0.5,
+                self.rect.h,
+            ),
+            Anchor::Left,
+            self.font,
+            layout::font_size(16),
+            BLACK,
+        );
+
+        draw_rectangle(
+            check_box.x,
+            check_box.y,
+            check_box.w,
+            check_box.h,
+            if self.click.is_held { LIGHTGRAY } else { WHITE },
+        );
+        draw_rectangle_lines(
+            check_box.x,
+            check_box.y,
+            check_box.w,
+            check_box.h,
+            2.,
+            BLACK,
+        );
+
+        // Draw a tick when checked
+        if self.is_checked {
+            draw_line(
+                check_box.x + size * 0.2,
+                check_box.y + size * 0.5,
+                check_box.x + size * 0.45,
+                check_box.y + size * 0.75,
+                3.,
+                BLACK,
+            );
+            draw_line(
+                check_box.x + size * 0.45,
+                check_box.y + size * 0.75,
+                check_box.x + size * 0.8,
+                check_box.y + size * 0.25,
+                3.,
+                BLACK,
+            );
+        }
+    }
+}
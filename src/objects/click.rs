@@ -0,0 +1,44 @@
+use macroquad::prelude::*;
+
+// Click detection shared by the widgets, a click happens when the mouse is
+// pressed and then released over the same widget
+#[derive(Clone, Copy, Default)]
+pub struct Click {
+    // Set while the mouse is held down after being pressed on the widget
+    pub is_held: bool,
+    // Only set for the frame the click happened
+    pub is_clicked: bool,
+    // Only set for the frame a disabled widget was clicked
+    pub is_rejected: bool,
+}
+
+impl Click {
+    pub fn update(&mut self, rect: Rect, is_focused: bool, is_disabled: bool) {
+        self.is_clicked = false;
+        self.is_rejected = false;
+
+        let is_hovered = rect.contains(mouse_position().into());
+
+        if is_mouse_button_pressed(MouseButton::Left) && is_hovered {
+            self.is_held = true;
+        }
+
+        if is_mouse_button_released(MouseButton::Left) {
+            self.is_clicked = self.is_held && is_hovered;
+            self.is_held = false;
+        }
+
+        // The keyboard can click the focused widget
+        if is_focused && is_key_pressed(KeyCode::Enter) {
+            self.is_clicked = true;
+        }
+
+        // Disabled widgets can't be clicked, but the click is remembered so
+        // the widget can say why
+        if is_disabled {
+            self.is_rejected = self.is_clicked;
+            self.is_clicked = false;
+            self.is_held = false;
+        }
+    }
+}
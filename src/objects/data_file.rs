@@ -0,0 +1,105 @@
+use macroquad::prelude::*;
+
+// A line of a data file that couldn't be read
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    // Counting from 1, like a text editor
+    pub line: usize,
+    pub message: String,
+}
+
+// A key=value line of a data file
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Entry<'a> {
+    pub line: usize,
+    pub key: &'a str,
+    pub value: &'a str,
+}
+
+impl Entry<'_> {
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            message: format!("{}: {}", self.key, message.into()),
+        }
+    }
+}
+
+// The key=value lines of files in res, like animations and maps
+// Empty lines and lines starting with # are skipped
+pub fn parse(file: &str) -> (Vec<Entry<'_>>, Vec<ParseError>) {
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, line) in file.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match line.split_once('=') {
+            Some((key, value)) => entries.push(Entry {
+                line: i + 1,
+                key: key.trim(),
+                value: value.trim(),
+            }),
+            None => errors.push(ParseError {
+                line: i + 1,
+                message: format!("expected key=value, found \"{}\"", line),
+            }),
+        }
+    }
+
+    (entries, errors)
+}
+
+// Bad lines are skipped, so the game still starts, but they are printed to be fixed
+pub fn report(path: &str, errors: &[ParseError]) {
+    for error in errors {
+        eprintln!("{}:{}: {}", path, error.line, error.message);
+    }
+}
+
+// Two numbers split by a comma, like 1.5,2
+pub fn parse_vec2(text: &str) -> Option<Vec2> {
+    let (x, y) = text.split_once(',')?;
+
+    Some(vec2(x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_comments_and_empty_lines() {
+        let (entries, errors) = parse("# A comment\n\n  mode = once  \n");
+
+        assert!(errors.is_empty());
+        assert_eq!(
+            entries,
+            vec![Entry {
+                line: 3,
+                key: "mode",
+                value: "once",
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_lines_without_a_value() {
+        let (entries, errors) = parse("mode=loop\nframe 0 0.1\n");
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 2);
+    }
+
+    #[test]
+    fn parses_vec2() {
+        assert_eq!(parse_vec2(" 1.5, 2 "), Some(vec2(1.5, 2.)));
+        assert_eq!(parse_vec2("1.5"), None);
+        assert_eq!(parse_vec2("a,2"), None);
+    }
+}
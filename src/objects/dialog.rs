@@ -0,0 +1,103 @@
+use crate::objects::button::Button;
+use crate::objects::focus::Focus;
+use crate::objects::layout::{self, Anchor, Stack, BUTTON_SIZE};
+
+use macroquad::prelude::*;
+
+const DIALOG_SIZE: Vec2 = const_vec2!([460., 200.]);
+
+// A box asking the player to confirm something, drawn over the screen
+pub struct Dialog {
+    pub is_open: bool,
+    pub message: String,
+
+    font: Font,
+    yes_button: Button,
+    no_button: Button,
+    focus: Focus,
+}
+
+impl Dialog {
+    pub async fn new() -> Self {
+        Self {
+            is_open: false,
+            message: String::new(),
+
+            font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
+            yes_button: Button::new(Vec2::ZERO, BUTTON_SIZE, "Yes".to_string(), GREEN, 15).await,
+            no_button: Button::new(Vec2::ZERO, BUTTON_SIZE, "No".to_string(), RED, 15).await,
+            focus: Focus::default(),
+        }
+    }
+
+    pub fn open(&mut self, message: String) {
+        self.is_open = true;
+        self.message = message;
+        self.focus.index = None;
+    }
+
+    // Returns the answer on the frame the player gives it, which closes the dialog
+    pub fn update(&mut self) -> Option<bool> {
+        if !self.is_open {
+            return None;
+        }
+
+        let row = Stack::row(Anchor::Center, BUTTON_SIZE, 2).with_offset(vec2(0., 50.));
+
+        self.focus.update(2);
+        self.yes_button.is_focused = self.focus.is_focused(0);
+        self.no_button.is_focused = self.focus.is_focused(1);
+
+        self.yes_button.set_rect(row.rect(0));
+        self.no_button.set_rect(row.rect(1));
+
+        let answer = if self.yes_button.is_clicked() {
+            Some(true)
+        } else if self.no_button.is_clicked() || is_key_pressed(KeyCode::Escape) {
+            Some(false)
+        } else {
+            None
+        };
+
+        if answer.is_some() {
+            self.is_open = false;
+        }
+
+        answer
+    }
+
+    pub async fn draw(&mut self) {
+        if !self.is_open {
+            return;
+        }
+
+        // Darken everything behind the dialog
+        draw_rectangle(
+            0.,
+            0.,
+            screen_width(),
+            screen_height(),
+            Color::new(0., 0., 0., 0.4),
+        );
+
+        let rect = layout::place(Anchor::Center, DIALOG_SIZE, Vec2::ZERO);
+
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, WHITE);
+        draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 3., BLACK);
+
+        // The message goes in the top half
+        let mut message_rect = rect;
+        message_rect.h *= 0.5;
+
+        layout::draw_text_centered(
+            self.message.as_str(),
+            message_rect,
+            self.font,
+            layout::font_size(20),
+            BLACK,
+        );
+
+        self.yes_button.draw().await;
+        self.no_button.draw().await;
+    }
+}
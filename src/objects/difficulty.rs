@@ -0,0 +1,57 @@
+#[derive(PartialEq, Clone, Copy, Default)]
+pub enum Difficulty {
+    Casual,
+    #[default]
+    Normal,
+    Hardcore,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Casual, Difficulty::Normal, Difficulty::Hardcore];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Casual => "Casual",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hardcore => "Hardcore",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Difficulty::Casual => "Keep your money and bullets when you die",
+            Difficulty::Normal => "Lose half of your money and bullets when you die",
+            Difficulty::Hardcore => "Dying ends the game and you start over as an apple",
+        }
+    }
+
+    // Fraction of money and bullets lost on death
+    pub fn death_penalty(&self) -> f32 {
+        match self {
+            Difficulty::Casual => 0.,
+            Difficulty::Normal => 0.5,
+            Difficulty::Hardcore => 1.,
+        }
+    }
+
+    pub fn is_permadeath(&self) -> bool {
+        *self == Difficulty::Hardcore
+    }
+
+    // How much faster enemies spawn
+    pub fn spawn_rate(&self) -> f32 {
+        match self {
+            Difficulty::Casual => 0.75,
+            Difficulty::Normal => 1.,
+            Difficulty::Hardcore => 1.5,
+        }
+    }
+
+    pub fn enemy_speed(&self) -> f32 {
+        match self {
+            Difficulty::Casual => 0.8,
+            Difficulty::Normal => 1.,
+            Difficulty::Hardcore => 1.25,
+        }
+    }
+}
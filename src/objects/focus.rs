@@ -0,0 +1,50 @@
+use crate::objects::button::Button;
+
+use macroquad::prelude::*;
+
+// Which widget on a screen the keyboard is controlling
+#[derive(Clone, Copy, Default)]
+pub struct Focus {
+    pub index: Option<usize>,
+}
+
+impl Focus {
+    // Tab and the up and down arrows move the focus between the widgets
+    pub fn update(&mut self, count: usize) {
+        let is_shift_down = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+
+        let is_next =
+            (is_key_pressed(KeyCode::Tab) && !is_shift_down) || is_key_pressed(KeyCode::Down);
+        let is_previous =
+            (is_key_pressed(KeyCode::Tab) && is_shift_down) || is_key_pressed(KeyCode::Up);
+
+        if count == 0 {
+            self.index = None;
+        } else if is_next {
+            self.index = Some(self.index.map_or(0, |i| (i + 1) % count));
+        } else if is_previous {
+            self.index = Some(self.index.map_or(count - 1, |i| (i + count - 1) % count));
+        }
+
+        // Using the mouse hides the focus
+        if is_mouse_button_pressed(MouseButton::Left) {
+            self.index = None;
+        }
+
+        // Widgets can disappear from under the focus
+        self.index = self.index.filter(|i| *i < count);
+    }
+
+    pub fn is_focused(&self, index: usize) -> bool {
+        self.index == Some(index)
+    }
+
+    // Move the focus between a list of buttons
+    pub fn update_buttons(&mut self, buttons: &mut [Button]) {
+        self.update(buttons.len());
+
+        for (i, button) in buttons.iter_mut().enumerate() {
+            button.is_focused = self.is_focused(i);
+        }
+    }
+}
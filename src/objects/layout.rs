@@ -0,0 +1,214 @@
+use macroquad::prelude::*;
+
+// The window height the UI was designed for
+const REFERENCE_HEIGHT: f32 = 600.;
+// Space between the UI and the edge of the screen
+pub const PADDING: f32 = 20.;
+// Space between items in a row or column
+pub const SPACING: f32 = 10.;
+pub const BUTTON_SIZE: Vec2 = const_vec2!([200., 50.]);
+
+// How much bigger the UI is than it was designed, so it fits any window size and DPI
+pub fn scale() -> f32 {
+    (screen_height() / REFERENCE_HEIGHT).clamp(0.75, 3.)
+}
+
+pub fn font_size(size: u16) -> u16 {
+    (size as f32 * scale()).round() as u16
+}
+
+// Where on the screen something is placed
+#[derive(Clone, Copy)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    // How far across and down the screen the anchor is, from 0 to 1
+    fn fraction(&self) -> Vec2 {
+        match self {
+            Anchor::TopLeft => vec2(0., 0.),
+            Anchor::Top => vec2(0.5, 0.),
+            Anchor::TopRight => vec2(1., 0.),
+            Anchor::Left => vec2(0., 0.5),
+            Anchor::Center => vec2(0.5, 0.5),
+            Anchor::BottomLeft => vec2(0., 1.),
+            Anchor::Bottom => vec2(0.5, 1.),
+            Anchor::BottomRight => vec2(1., 1.),
+        }
+    }
+}
+
+// Place a box inside the padded screen, size and offset are in unscaled units
+pub fn place(anchor: Anchor, size: Vec2, offset: Vec2) -> Rect {
+    let scale = scale();
+    let padding = PADDING * scale;
+    let area = vec2(
+        screen_width() - padding * 2.,
+        screen_height() - padding * 2.,
+    );
+
+    let pos = vec2(padding, padding) + (area - size * scale) * anchor.fraction() + offset * scale;
+
+    Rect::new(pos.x, pos.y, size.x * scale, size.y * scale)
+}
+
+#[derive(Clone, Copy)]
+pub enum Direction {
+    Row,
+    Column,
+}
+
+// Equally sized items placed next to each other, anchored as a whole
+#[derive(Clone, Copy)]
+pub struct Stack {
+    pub anchor: Anchor,
+    pub direction: Direction,
+    pub item_size: Vec2,
+    pub count: usize,
+    pub offset: Vec2,
+}
+
+impl Stack {
+    pub fn column(anchor: Anchor, item_size: Vec2, count: usize) -> Self {
+        Self {
+            anchor,
+            direction: Direction::Column,
+            item_size,
+            count,
+            offset: Vec2::ZERO,
+        }
+    }
+
+    pub fn row(anchor: Anchor, item_size: Vec2, count: usize) -> Self {
+        Self {
+            anchor,
+            direction: Direction::Row,
+            item_size,
+            count,
+            offset: Vec2::ZERO,
+        }
+    }
+
+    pub fn with_offset(mut self, offset: Vec2) -> Self {
+        self.offset = offset;
+
+        self
+    }
+
+    // Distance from one item to the next
+    fn step(&self) -> Vec2 {
+        match self.direction {
+            Direction::Row => vec2(self.item_size.x + SPACING, 0.),
+            Direction::Column => vec2(0., self.item_size.y + SPACING),
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.item_size + self.step() * self.count.saturating_sub(1) as f32
+    }
+
+    pub fn rect(&self, index: usize) -> Rect {
+        let area = place(self.anchor, self.size(), self.offset);
+        let pos = area.point() + self.step() * index as f32 * scale();
+
+        Rect::new(
+            pos.x,
+            pos.y,
+            self.item_size.x * scale(),
+            self.item_size.y * scale(),
+        )
+    }
+}
+
+fn params(font: Font, font_size: u16, color: Color) -> TextParams {
+    TextParams {
+        font,
+        font_size,
+        color,
+        font_scale: 1.,
+        font_scale_aspect: 1.,
+    }
+}
+
+// Draw text lined up inside a rect, the font size is already scaled
+pub fn draw_text_in(
+    text: &str,
+    rect: Rect,
+    anchor: Anchor,
+    font: Font,
+    font_size: u16,
+    color: Color,
+) {
+    let dimensions = measure_text(text, Some(font), font_size, 1.);
+    let space = rect.size() - vec2(dimensions.width, dimensions.height);
+    let pos = rect.point() + space * anchor.fraction();
+
+    draw_text_ex(
+        text,
+        pos.x,
+        pos.y + dimensions.offset_y,
+        params(font, font_size, color),
+    );
+}
+
+pub fn draw_text_centered(text: &str, rect: Rect, font: Font, font_size: u16, color: Color) {
+    draw_text_in(text, rect, Anchor::Center, font, font_size, color);
+}
+
+// Draw text anchored on the screen and return where it was drawn
+pub fn draw_text_anchored(
+    text: &str,
+    anchor: Anchor,
+    offset: Vec2,
+    font: Font,
+    font_size: u16,
+    color: Color,
+) -> Rect {
+    let font_size = self::font_size(font_size);
+    let dimensions = measure_text(text, Some(font), font_size, 1.);
+
+    let rect = place(
+        anchor,
+        vec2(dimensions.width, dimensions.height) / scale(),
+        offset,
+    );
+
+    draw_text_ex(
+        text,
+        rect.x,
+        rect.y + dimensions.offset_y,
+        params(font, font_size, color),
+    );
+
+    rect
+}
+
+// Draw lines of text under each other starting at an anchor
+pub fn draw_lines(
+    lines: &[String],
+    anchor: Anchor,
+    offset: Vec2,
+    line_height: f32,
+    font: Font,
+    font_size: u16,
+    color: Color,
+) {
+    for (i, line) in lines.iter().enumerate() {
+        draw_text_anchored(
+            line.as_str(),
+            anchor,
+            offset + vec2(0., i as f32 * line_height),
+            font,
+            font_size,
+            color,
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use crate::objects::layout::{self, Anchor};
+use crate::objects::save_file;
+use crate::objects::stats::Stats;
+
+use macroquad::prelude::*;
+
+const LEADERBOARD_FILE: &str = "leaderboard.txt";
+const MAX_ENTRIES: usize = 10;
+// Seconds of survival per wave
+pub const WAVE_TIME: f32 = 30.;
+// Size of the leaderboard table in unscaled units
+const TABLE_WIDTH: f32 = 520.;
+const ROW_HEIGHT: f32 = 25.;
+// Mixed into the checksum so the file can't be edited by just recomputing a plain hash
+const CHECKSUM_SALT: &str = "becoming the banana man";
+
+pub fn wave(survival_time: f32) -> usize {
+    1 + (survival_time / WAVE_TIME) as usize
+}
+
+#[derive(Clone)]
+pub struct Entry {
+    pub name: String,
+    pub score: usize,
+    pub kills: usize,
+    pub money: usize,
+    pub survival_time: f32,
+    pub wave: usize,
+}
+
+impl Entry {
+    pub fn new(name: String, run: &Stats) -> Self {
+        Self {
+            name,
+            score: score(run),
+            kills: run.kills,
+            money: run.money_earned,
+            survival_time: run.time_played,
+            wave: wave(run.time_played),
+        }
+    }
+}
+
+// Size of a table showing some rows under the header, for placing it with the layout
+pub fn table_size(rows: usize) -> Vec2 {
+    vec2(TABLE_WIDTH, (rows + 1) as f32 * ROW_HEIGHT)
+}
+
+pub fn score(run: &Stats) -> usize {
+    run.kills * 10 + run.money_earned + run.time_played as usize * 2 + wave(run.time_played) * 50
+}
+
+// FNV-1a hash of the salted file contents
+fn checksum(contents: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for byte in CHECKSUM_SALT.bytes().chain(contents.bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash
+}
+
+#[derive(Clone, Default)]
+pub struct Leaderboard {
+    pub entries: Vec<Entry>,
+    // Set when the saved file was edited by hand
+    pub was_tampered: bool,
+}
+
+impl Leaderboard {
+    pub fn load() -> Self {
+        let mut leaderboard = Self::default();
+
+        // Start with an empty leaderboard if nothing has been saved yet
+        let Some(file) = save_file::load(LEADERBOARD_FILE) else {
+            return leaderboard;
+        };
+
+        // The last line is the checksum of everything before it
+        let Some((contents, saved_checksum)) = file.trim_end().rsplit_once('\n') else {
+            return leaderboard;
+        };
+
+        if saved_checksum.parse::<u64>().ok() != Some(checksum(contents)) {
+            leaderboard.was_tampered = true;
+
+            return leaderboard;
+        }
+
+        for line in contents.lines() {
+            let fields: Vec<&str> = line.split('\t').collect();
+
+            if let [name, score, kills, money, survival_time, wave] = fields[..] {
+                leaderboard.entries.push(Entry {
+                    name: name.to_string(),
+                    score: score.parse().unwrap_or_default(),
+                    kills: kills.parse().unwrap_or_default(),
+                    money: money.parse().unwrap_or_default(),
+                    survival_time: survival_time.parse().unwrap_or_default(),
+                    wave: wave.parse().unwrap_or_default(),
+                });
+            }
+        }
+
+        leaderboard
+    }
+
+    pub fn save(&self) {
+        let mut contents = "banana_man leaderboard".to_string();
+
+        for entry in &self.entries {
+            contents += format!(
+                "\n{}\t{}\t{}\t{}\t{}\t{}",
+                entry.name, entry.score, entry.kills, entry.money, entry.survival_time, entry.wave
+            )
+            .as_str();
+        }
+
+        let file = format!("{}\n{}\n", contents, checksum(&contents));
+
+        save_file::save(LEADERBOARD_FILE, &file);
+    }
+
+    // Check if a score would make it onto the leaderboard
+    pub fn qualifies(&self, score: usize) -> bool {
+        score > 0
+            && (self.entries.len() < MAX_ENTRIES
+                || self.entries.iter().any(|entry| score > entry.score))
+    }
+
+    // Draw the table inside a rect placed with table_size
+    pub fn draw(&self, rect: Rect, rows: usize, highlight: Option<usize>, font: Font) {
+        const COLUMNS: [(&str, f32); 6] = [
+            ("#", 0.),
+            ("Name", 40.),
+            ("Score", 220.),
+            ("Kills", 310.),
+            ("Money", 390.),
+            ("Wave", 480.),
+        ];
+
+        let scale = layout::scale();
+        let font_size = layout::font_size(18);
+
+        // Each cell runs from its column to the right of the table
+        let cell = |x: f32, row: usize| {
+            Rect::new(
+                rect.x + x * scale,
+                rect.y + row as f32 * ROW_HEIGHT * scale,
+                rect.w - x * scale,
+                ROW_HEIGHT * scale,
+            )
+        };
+
+        // Draw the header
+        for (title, x) in COLUMNS {
+            layout::draw_text_in(title, cell(x, 0), Anchor::Left, font, font_size, DARKGRAY);
+        }
+
+        if self.was_tampered {
+            layout::draw_text_in(
+                "The saved leaderboard was edited and has been reset",
+                cell(0., 1),
+                Anchor::Left,
+                font,
+                font_size,
+                RED,
+            );
+        }
+
+        for (i, entry) in self.entries.iter().take(rows).enumerate() {
+            let color = if highlight == Some(i) { RED } else { BLACK };
+
+            let values = [
+                (i + 1).to_string(),
+                entry.name.clone(),
+                entry.score.to_string(),
+                entry.kills.to_string(),
+                format!("${}", entry.money),
+                entry.wave.to_string(),
+            ];
+
+            for ((_, x), value) in COLUMNS.iter().zip(values.iter()) {
+                layout::draw_text_in(
+                    value.as_str(),
+                    cell(*x, i + 1),
+                    Anchor::Left,
+                    font,
+                    font_size,
+                    color,
+                );
+            }
+        }
+    }
+
+    // Returns where the entry was placed
+    pub fn add(&mut self, entry: Entry) -> usize {
+        let place = self
+            .entries
+            .iter()
+            .position(|r| entry.score > r.score)
+            .unwrap_or(self.entries.len());
+
+        self.entries.insert(place, entry);
+        self.entries.truncate(MAX_ENTRIES);
+
+        self.was_tampered = false;
+        self.save();
+
+        place
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENTS: &str = "banana_man leaderboard\nDequog\t1250\t40\t300\t95.5\t4";
+
+    #[test]
+    fn checksum_changes_when_one_byte_is_edited() {
+        let original = checksum(CONTENTS);
+
+        for i in 0..CONTENTS.len() {
+            let mut edited = CONTENTS.as_bytes().to_vec();
+            edited[i] = if edited[i] == b'9' { b'8' } else { b'9' };
+
+            let edited = String::from_utf8(edited).unwrap();
+            assert_ne!(checksum(&edited), original, "edited byte {}", i);
+        }
+    }
+}
@@ -1,4 +1,20 @@
+pub mod achievements;
+pub mod animation;
 pub mod bullets;
 pub mod button;
+pub mod checkbox;
+pub mod click;
+pub mod data_file;
+pub mod dialog;
+pub mod difficulty;
+pub mod focus;
+pub mod layout;
+pub mod leaderboard;
 pub mod money;
+pub mod save_file;
+pub mod settings;
+pub mod slider;
+pub mod stats;
 pub mod text;
+pub mod text_input;
+pub mod tooltip;
@@ -1,3 +1,4 @@
+use crate::objects::layout::Anchor;
 use crate::objects::text::Text;
 use macroquad::prelude::*;
 
@@ -12,10 +13,8 @@ impl Money {
         Self {
             money: 0,
             text: Text::new(
-                vec2(
-                    screen_width() * 0.5 - 7.5 - "$0".chars().count() as f32 * 7.5,
-                    40.,
-                ),
+                Anchor::Top,
+                vec2(0., 0.),
                 "res/Roboto-Medium.ttf".to_string(),
                 format!("${}", 0.to_string()),
                 30,
@@ -26,13 +25,6 @@ impl Money {
     }
 
     pub fn increment(&mut self, amount: usize) {
-        self.text.pos = vec2(
-            screen_width() * 0.5
-                - 7.5
-                - format!("${}", self.money.to_string()).chars().count() as f32 * 7.5,
-            40.,
-        );
-
         // Increment the money
         self.money += amount;
     }
@@ -44,13 +36,6 @@ impl Money {
     pub fn draw(&mut self) {
         self.text.change(format!("${}", self.money.to_string()));
 
-        self.text.pos = vec2(
-            screen_width() * 0.5
-                - 7.5
-                - format!("${}", self.money.to_string()).chars().count() as f32 * 7.5,
-            40.,
-        );
-
         // Draw the Money
         self.text.draw();
     }
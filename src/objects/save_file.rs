@@ -0,0 +1,20 @@
+use std::fs;
+
+// Everything the game saves goes in this folder next to the game
+const SAVE_DIR: &str = "save";
+
+fn path(name: &str) -> String {
+    format!("{}/{}", SAVE_DIR, name)
+}
+
+// The contents of a saved file, None if it hasn't been saved yet
+pub fn load(name: &str) -> Option<String> {
+    fs::read_to_string(path(name)).ok()
+}
+
+pub fn save(name: &str, contents: &str) {
+    // Saving is best effort, the game keeps going if it fails
+    if fs::create_dir_all(SAVE_DIR).is_ok() {
+        fs::write(path(name), contents).ok();
+    }
+}
@@ -0,0 +1,94 @@
+use crate::objects::save_file;
+
+const SETTINGS_FILE: &str = "settings.txt";
+
+#[derive(Clone)]
+pub struct Settings {
+    pub is_music_on: bool,
+    // From 0 to 1
+    pub music_volume: f32,
+    // From 0 to 1, how many particles are spawned
+    pub particle_density: f32,
+    // From 0 to 1, 0 turns the screen shake off
+    pub screen_shake: f32,
+    pub is_hit_stop_on: bool,
+    pub is_zoom_punch_on: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            is_music_on: true,
+            music_volume: 0.5,
+            particle_density: 1.,
+            screen_shake: 1.,
+            is_hit_stop_on: true,
+            is_zoom_punch_on: true,
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        let mut settings = Self::default();
+
+        // Use the default settings if nothing has been saved yet
+        let Some(file) = save_file::load(SETTINGS_FILE) else {
+            return settings;
+        };
+
+        for line in file.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+
+            let value = value.trim();
+
+            match key.trim() {
+                "is_music_on" => settings.is_music_on = value == "true",
+                "music_volume" => {
+                    settings.music_volume = value
+                        .parse::<f32>()
+                        .map_or(settings.music_volume, |volume| volume.clamp(0., 1.))
+                }
+                "particle_density" => {
+                    settings.particle_density = value
+                        .parse::<f32>()
+                        .map_or(settings.particle_density, |density| density.clamp(0., 1.))
+                }
+                "screen_shake" => {
+                    settings.screen_shake = value
+                        .parse::<f32>()
+                        .map_or(settings.screen_shake, |shake| shake.clamp(0., 1.))
+                }
+                "is_hit_stop_on" => settings.is_hit_stop_on = value == "true",
+                "is_zoom_punch_on" => settings.is_zoom_punch_on = value == "true",
+                _ => {}
+            }
+        }
+
+        settings
+    }
+
+    pub fn save(&self) {
+        let file = format!(
+            "is_music_on={}\nmusic_volume={}\nparticle_density={}\nscreen_shake={}\nis_hit_stop_on={}\nis_zoom_punch_on={}\n",
+            self.is_music_on,
+            self.music_volume,
+            self.particle_density,
+            self.screen_shake,
+            self.is_hit_stop_on,
+            self.is_zoom_punch_on
+        );
+
+        save_file::save(SETTINGS_FILE, &file);
+    }
+
+    pub fn music_volume(&self) -> f32 {
+        if self.is_music_on {
+            self.music_volume
+        } else {
+            0.
+        }
+    }
+}
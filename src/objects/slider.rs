@@ -0,0 +1,132 @@
+use crate::objects::layout::{self, Anchor};
+
+use macroquad::prelude::*;
+
+pub struct Slider {
+    pub rect: Rect,
+    pub label: String,
+    pub value: f32,
+    pub is_focused: bool,
+
+    min: f32,
+    max: f32,
+    // The value snaps to multiples of this
+    step: f32,
+    font: Font,
+    is_dragging: bool,
+}
+
+impl Slider {
+    pub async fn new(label: String, min: f32, max: f32, step: f32, value: f32) -> Self {
+        Self {
+            rect: Rect::new(0., 0., 0., 0.),
+            label,
+            value,
+            is_focused: false,
+
+            min,
+            max,
+            step,
+            font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
+            is_dragging: false,
+        }
+    }
+
+    // The label goes on the left and the track on the right
+    fn track(&self) -> Rect {
+        Rect::new(
+            self.rect.x + self.rect.w * 0.5,
+            self.rect.y + self.rect.h * 0.4,
+            self.rect.w * 0.5,
+            self.rect.h * 0.2,
+        )
+    }
+
+    fn set(&mut self, value: f32) {
+        self.value = ((value / self.step).round() * self.step).clamp(self.min, self.max);
+    }
+
+    // Must be called once every frame
+    pub fn update(&mut self, rect: Rect) {
+        self.rect = rect;
+
+        let track = self.track();
+        // The knob can be grabbed a little outside of the track
+        let grab_area = Rect::new(track.x, self.rect.y, track.w, self.rect.h);
+
+        if is_mouse_button_pressed(MouseButton::Left) && grab_area.contains(mouse_position().into())
+        {
+            self.is_dragging = true;
+        }
+
+        if !is_mouse_button_down(MouseButton::Left) {
+            self.is_dragging = false;
+        }
+
+        if self.is_dragging {
+            let fraction = ((mouse_position().0 - track.x) / track.w).clamp(0., 1.);
+
+            self.set(self.min + fraction * (self.max - self.min));
+        }
+
+        // The arrow keys move the focused slider
+        if self.is_focused {
+            if is_key_pressed(KeyCode::Left) {
+                self.set(self.value - self.step);
+            }
+
+            if is_key_pressed(KeyCode::Right) {
+                self.set(self.value + self.step);
+            }
+        }
+    }
+
+    pub fn draw(&self) {
+        let track = self.track();
+        let fraction = (self.value - self.min) / (self.max - self.min);
+
+        if self.is_focused {
+            draw_rectangle_lines(
+                self.rect.x,
+                self.rect.y,
+                self.rect.w,
+                self.rect.h,
+                4.,
+                BLACK,
+            );
+        }
+
+        layout::draw_text_in(
+            format!("{}: {}", self.label, self.value).as_str(),
+            Rect::new(
+                self.rect.x + 10.,
+                self.rect.y,
+                self.rect.w * 0.5,
+                self.rect.h,
+            ),
+            Anchor::Left,
+            self.font,
+            layout::font_size(16),
+            BLACK,
+        );
+
+        // Draw the track, filled up to the value
+        draw_rectangle(track.x, track.y, track.w, track.h, DARKGRAY);
+        draw_rectangle(track.x, track.y, track.w * fraction, track.h, RED);
+
+        // Draw the knob
+        draw_circle(
+            track.x + track.w * fraction,
+            track.y + track.h * 0.5,
+            track.h * 1.2,
+            if self.is_dragging { MAROON } else { WHITE },
+        );
+        draw_circle_lines(
+            track.x + track.w * fraction,
+            track.y + track.h * 0.5,
+            track.h * 1.2,
+            2.,
+            BLACK,
+        );
+    }
+}
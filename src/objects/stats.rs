@@ -0,0 +1,144 @@
+use crate::objects::save_file;
+use crate::upgrading::tier::TIERS;
+
+const STATS_FILE: &str = "stats.txt";
+
+// Something that happened in the game that the stats keep track of
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum StatEvent {
+    ShotFired,
+    Hit,
+    Kill,
+    BossDefeated,
+    MoneyEarned(usize),
+    MoneySpent(usize),
+    BulletsBought(usize),
+    Death,
+    Retreat,
+    // Only tracked by achievements
+    HardcoreDeath,
+    NewGame,
+    TierReached(usize),
+    HighScore,
+}
+
+#[derive(Clone, Default)]
+pub struct Stats {
+    // Seconds spent battling and upgrading
+    pub time_played: f32,
+    pub time_per_tier: [f32; TIERS.len()],
+
+    pub shots_fired: usize,
+    pub hits: usize,
+    pub kills: usize,
+    pub bosses_defeated: usize,
+
+    pub money_earned: usize,
+    pub money_spent: usize,
+    pub bullets_bought: usize,
+
+    pub deaths: usize,
+    pub retreats: usize,
+}
+
+impl Stats {
+    pub fn record(&mut self, event: StatEvent) {
+        match event {
+            StatEvent::ShotFired => self.shots_fired += 1,
+            StatEvent::Hit => self.hits += 1,
+            StatEvent::Kill => self.kills += 1,
+            StatEvent::BossDefeated => self.bosses_defeated += 1,
+            StatEvent::MoneyEarned(amount) => self.money_earned += amount,
+            StatEvent::MoneySpent(amount) => self.money_spent += amount,
+            StatEvent::BulletsBought(amount) => self.bullets_bought += amount,
+            StatEvent::Death => self.deaths += 1,
+            StatEvent::Retreat => self.retreats += 1,
+            StatEvent::HardcoreDeath
+            | StatEvent::NewGame
+            | StatEvent::TierReached(_)
+            | StatEvent::HighScore => {}
+        }
+    }
+
+    pub fn add_time(&mut self, seconds: f32, tier: usize) {
+        self.time_played += seconds;
+        self.time_per_tier[tier] += seconds;
+    }
+
+    // Percentage of shots that hit something
+    pub fn accuracy(&self) -> f32 {
+        if self.shots_fired == 0 {
+            return 0.;
+        }
+
+        self.hits as f32 / self.shots_fired as f32 * 100.
+    }
+
+    pub fn load() -> Self {
+        let mut stats = Self::default();
+
+        // Start with empty stats if nothing has been saved yet
+        let Some(file) = save_file::load(STATS_FILE) else {
+            return stats;
+        };
+
+        for line in file.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+
+            let number = value.trim().parse::<f32>().unwrap_or_default();
+
+            match key.trim() {
+                "time_played" => stats.time_played = number,
+                "shots_fired" => stats.shots_fired = number as usize,
+                "hits" => stats.hits = number as usize,
+                "kills" => stats.kills = number as usize,
+                "bosses_defeated" => stats.bosses_defeated = number as usize,
+                "money_earned" => stats.money_earned = number as usize,
+                "money_spent" => stats.money_spent = number as usize,
+                "bullets_bought" => stats.bullets_bought = number as usize,
+                "deaths" => stats.deaths = number as usize,
+                "retreats" => stats.retreats = number as usize,
+                key => {
+                    // Time per tier is saved as time_<tier name>
+                    for (i, tier) in TIERS.iter().enumerate() {
+                        if key == format!("time_{}", tier.name.to_lowercase()) {
+                            stats.time_per_tier[i] = number;
+                        }
+                    }
+                }
+            }
+        }
+
+        stats
+    }
+
+    pub fn save(&self) {
+        let mut file = format!(
+            "time_played={}\nshots_fired={}\nhits={}\nkills={}\nbosses_defeated={}\n\
+             money_earned={}\nmoney_spent={}\nbullets_bought={}\ndeaths={}\nretreats={}\n",
+            self.time_played,
+            self.shots_fired,
+            self.hits,
+            self.kills,
+            self.bosses_defeated,
+            self.money_earned,
+            self.money_spent,
+            self.bullets_bought,
+            self.deaths,
+            self.retreats,
+        );
+
+        for (i, tier) in TIERS.iter().enumerate() {
+            file += format!(
+                "time_{}={}\n",
+                tier.name.to_lowercase(),
+                self.time_per_tier[i]
+            )
+            .as_str();
+        }
+
+        save_file::save(STATS_FILE, &file);
+    }
+}
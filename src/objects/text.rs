@@ -1,22 +1,34 @@
+use crate::objects::layout::{self, Anchor};
+
 use macroquad::prelude::*;
 
 #[derive(Clone)]
 pub struct Text {
     pub font: Font,
     pub text: String,
-    pub pos: Vec2,
+    // Where the text is placed on the screen
+    pub anchor: Anchor,
+    pub offset: Vec2,
     pub font_size: u16,
     pub color: Color,
 }
 
 impl Text {
-    pub async fn new(pos: Vec2, font: String, text: String, font_size: u16, color: Color) -> Self {
+    pub async fn new(
+        anchor: Anchor,
+        offset: Vec2,
+        font: String,
+        text: String,
+        font_size: u16,
+        color: Color,
+    ) -> Self {
         Self {
             font: load_ttf_font(font.as_str()).await.unwrap(),
             /* Text must be converted to a char array because a struct with a String
             field cannot implement the Copy trait*/
             text: text,
-            pos,
+            anchor,
+            offset,
             font_size,
             color,
         }
@@ -29,10 +41,24 @@ impl Text {
 
     pub fn draw(&mut self) {
         // Draw the Text
+        layout::draw_text_anchored(
+            self.text.as_str(),
+            self.anchor,
+            self.offset,
+            self.font,
+            self.font_size,
+            self.color,
+        );
+    }
+
+    // Draw the Text centred on a point of the field instead of anchored on the screen
+    pub fn draw_at(&self, pos: Vec2) {
+        let dimensions = measure_text(self.text.as_str(), Some(self.font), self.font_size, 1.);
+
         draw_text_ex(
             self.text.as_str(),
-            self.pos.x,
-            self.pos.y,
+            pos.x - dimensions.width * 0.5,
+            pos.y - dimensions.height * 0.5 + dimensions.offset_y,
             TextParams {
                 font: self.font,
                 font_size: self.font_size,
@@ -0,0 +1,88 @@
+use crate::objects::layout;
+
+use macroquad::prelude::*;
+
+pub struct TextInput {
+    pub rect: Rect,
+    pub text: String,
+    // Only lets digits be typed
+    pub is_numeric: bool,
+    max_length: usize,
+    font: Font,
+}
+
+impl TextInput {
+    pub async fn new(pos: Vec2, size: Vec2, max_length: usize) -> Self {
+        Self {
+            rect: Rect::new(pos.x, pos.y, size[0], size[1]),
+            text: String::new(),
+            is_numeric: false,
+            max_length,
+            font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
+        }
+    }
+
+    pub fn update(&mut self, pos: Vec2) {
+        self.rect.x = pos.x;
+        self.rect.y = pos.y;
+
+        // Type the characters pressed this frame
+        while let Some(character) = get_char_pressed() {
+            let is_allowed = if self.is_numeric {
+                character.is_ascii_digit()
+            } else {
+                character.is_ascii_alphanumeric() || character == ' '
+            };
+
+            if is_allowed && self.text.chars().count() < self.max_length {
+                self.text.push(character);
+            }
+        }
+
+        if is_key_pressed(KeyCode::Backspace) {
+            self.text.pop();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+
+        // Throw away anything typed before the input was shown
+        while get_char_pressed().is_some() {}
+    }
+
+    pub fn draw(&self) {
+        draw_rectangle(self.rect.x, self.rect.y, self.rect.w, self.rect.h, WHITE);
+        draw_rectangle_lines(
+            self.rect.x,
+            self.rect.y,
+            self.rect.w,
+            self.rect.h,
+            2.,
+            BLACK,
+        );
+
+        // Draw the text with a blinking cursor
+        draw_text_ex(
+            format!(
+                "{}{}",
+                self.text,
+                if (get_time() * 2.) as i32 % 2 == 0 {
+                    "|"
+                } else {
+                    ""
+                }
+            )
+            .as_str(),
+            self.rect.x + 10.,
+            self.rect.y + self.rect.h * 0.5 + 8. * layout::scale(),
+            TextParams {
+                font: self.font,
+                font_size: layout::font_size(22),
+                color: BLACK,
+                font_scale: 1.,
+                font_scale_aspect: 1.,
+            },
+        );
+    }
+}
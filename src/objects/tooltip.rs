@@ -0,0 +1,33 @@
+use crate::objects::layout;
+
+use macroquad::prelude::*;
+
+// Draw a box of text next to the mouse
+pub fn draw(text: &str, font: Font) {
+    let font_size = layout::font_size(16);
+    let padding = 8. * layout::scale();
+    let dimensions = measure_text(text, Some(font), font_size, 1.);
+    let size = vec2(
+        dimensions.width + padding * 2.,
+        dimensions.height + padding * 2.,
+    );
+
+    // Keep the tooltip on the screen
+    let mouse = Vec2::from(mouse_position());
+    let pos = vec2(
+        (mouse.x + padding * 2.).min(screen_width() - size.x),
+        (mouse.y - size.y).max(0.),
+    );
+    let rect = Rect::new(pos.x, pos.y, size.x, size.y);
+
+    draw_rectangle(
+        rect.x,
+        rect.y,
+        rect.w,
+        rect.h,
+        Color::new(1., 1., 0.9, 0.95),
+    );
+    draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 2., BLACK);
+
+    layout::draw_text_centered(text, rect, font, font_size, BLACK);
+}
@@ -0,0 +1,116 @@
+use crate::objects::achievements::{Achievements, ACHIEVEMENTS};
+use crate::objects::button::Button;
+use crate::objects::layout::{self, Anchor, Stack, BUTTON_SIZE};
+use crate::states::game_state::GameState;
+
+use macroquad::prelude::*;
+
+// Size of an achievement card in unscaled units
+const CARD_SIZE: Vec2 = const_vec2!([360., 65.]);
+// Space between the edge of a card and its text
+const CARD_PADDING: f32 = 10.;
+
+pub struct AchievementGallery {
+    pub game_state: GameState,
+    pub achievements: Achievements,
+
+    title: String,
+    back_button: Button,
+    font: Font,
+}
+
+impl AchievementGallery {
+    pub async fn new() -> Self {
+        Self {
+            game_state: GameState::Achievements,
+            achievements: Achievements::default(),
+
+            title: "Achievements".to_string(),
+            back_button: Button::new(Vec2::ZERO, BUTTON_SIZE, "Back".to_string(), RED, 15).await,
+            font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
+        }
+    }
+
+    pub async fn start(&mut self) {
+        let font = self.font;
+
+        // Draw title
+        layout::draw_text_anchored(
+            self.title.as_str(),
+            Anchor::Top,
+            Vec2::ZERO,
+            font,
+            30,
+            BLACK,
+        );
+
+        layout::draw_text_anchored(
+            format!(
+                "{}/{} unlocked",
+                self.achievements.unlocked_count(),
+                ACHIEVEMENTS.len()
+            )
+            .as_str(),
+            Anchor::Top,
+            vec2(0., 40.),
+            font,
+            18,
+            BLACK,
+        );
+
+        // Draw the achievements in two columns
+        let half = ACHIEVEMENTS.len().div_ceil(2);
+        let padding = CARD_PADDING * layout::scale();
+
+        for (i, achievement) in ACHIEVEMENTS.iter().enumerate() {
+            let column = Stack::column(Anchor::Top, CARD_SIZE, half).with_offset(vec2(
+                if i < half { -0.5 } else { 0.5 } * (CARD_SIZE.x + layout::SPACING),
+                80.,
+            ));
+            let card = column.rect(i % half);
+
+            // Locked achievements are greyed out
+            let (background, color) = if self.achievements.unlocked[i] {
+                (GOLD, BLACK)
+            } else {
+                (LIGHTGRAY, DARKGRAY)
+            };
+
+            draw_rectangle(card.x, card.y, card.w, card.h, background);
+            draw_rectangle_lines(card.x, card.y, card.w, card.h, 2., color);
+
+            // The name goes in the top half of the card and the description in the bottom half
+            let text_area = Rect::new(
+                card.x + padding,
+                card.y,
+                card.w - padding * 2.,
+                card.h * 0.5,
+            );
+
+            layout::draw_text_in(
+                achievement.name,
+                text_area.offset(vec2(0., padding * 0.5)),
+                Anchor::Left,
+                font,
+                layout::font_size(22),
+                color,
+            );
+            layout::draw_text_in(
+                achievement.description,
+                text_area.offset(vec2(0., card.h * 0.5 - padding * 0.5)),
+                Anchor::Left,
+                font,
+                layout::font_size(16),
+                color,
+            );
+        }
+
+        self.back_button
+            .set_rect(layout::place(Anchor::TopLeft, BUTTON_SIZE, Vec2::ZERO));
+        self.back_button.draw().await;
+
+        if self.back_button.is_clicked() {
+            self.game_state = GameState::Menu;
+        }
+    }
+}
@@ -1,17 +1,58 @@
+use crate::battling::boomerang::Boomerang;
+use crate::battling::boss::{Boss, BOSSES};
 use crate::battling::bullet::Bullet;
-use crate::battling::enemy::Enemy;
+use crate::battling::camera::CameraEffects;
+use crate::battling::enemy::{Enemy, ENEMY_SPEED};
+use crate::battling::floating_text::{self, FloatingTexts};
+use crate::battling::obstacle::{self, Map, Obstacle, ObstacleKind};
+use crate::battling::particles::{self, Particles};
+use crate::battling::pickup::{Pickup, PickupKind};
+use crate::battling::player::ability::{Ability, AbilityKind};
 use crate::battling::player::gun::Gun;
 use crate::battling::player::player::Player;
+use crate::battling::power_up::{Buffs, PowerUp, PowerUpKind};
+use crate::battling::steering::{self, Boid};
+use crate::objects::animation::{AnimationData, Effect};
 use crate::objects::bullets::Bullets;
 use crate::objects::button::Button;
+use crate::objects::difficulty::Difficulty;
+use crate::objects::layout::{self, Anchor, BUTTON_SIZE};
+use crate::objects::leaderboard;
 use crate::objects::money::Money;
+use crate::objects::stats::{StatEvent, Stats};
+use crate::objects::text::Text;
+use crate::states::dead::{DeathCause, DeathReport};
 use crate::states::game_state::GameState;
 use crate::upgrading::character::Character;
+use crate::upgrading::tier;
+use crate::upgrading::upgrades::{Stat, Upgrades};
 
 use macroquad::audio::{load_sound, play_sound, PlaySoundParams};
 use macroquad::prelude::*;
 
 const ENEMY_SPAWN_TIME: f32 = 50.;
+const NOVA_BULLETS: usize = 12;
+const SHIELD_RADIUS: f32 = 80.;
+const MAGAZINE_SIZE: f32 = 10.;
+const RELOAD_TIME: f32 = 1.5;
+const MAGNET_RADIUS: f32 = 60.;
+const AMMO_DROP_CHANCE: f32 = 0.15;
+// (Min, Max) seconds between power-ups spawning
+const POWER_UP_SPAWN_TIME: (f32, f32) = (8., 15.);
+// Places to try for a power-up before giving up on it
+const POWER_UP_SPAWN_TRIES: usize = 32;
+const TRIPLE_SHOT_SPREAD: f32 = 0.25;
+const SLOW_MOTION_FACTOR: f32 = 0.4;
+// Extra enemy health and money for every New Game+
+const NEW_GAME_PLUS_SCALING: f32 = 0.5;
+// Seconds of endless battling for enemies to spawn twice as fast
+const ENDLESS_RAMP_TIME: f32 = 60.;
+const WAVE_SPAWN_SCALING: f32 = 0.1;
+// Seconds the battle freezes for when an enemy is killed
+const KILL_HIT_STOP: f32 = 0.04;
+const KILL_TRAUMA: f32 = 0.1;
+const PLAYER_HIT_TRAUMA: f32 = 0.6;
+const EXPLOSION_TRAUMA: f32 = 0.8;
 
 pub struct Battling {
     pub game_state: GameState,
@@ -19,21 +60,63 @@ pub struct Battling {
     pub money: Money,
     pub bullet_count: Bullets,
     pub character: Character,
+    pub upgrades: Upgrades,
     pub gun: Gun,
 
-    pub out_of_bullets: bool,
+    pub death_report: DeathReport,
+    // Seed to use for the next run instead of a random one
+    pub next_seed: Option<u64>,
+
+    // Set by the upgrading screen to start a boss fight
+    pub is_boss_fight: bool,
+    pub bosses_defeated: usize,
+
+    // Things that happened this frame, for the stats to record
+    pub stat_events: Vec<StatEvent>,
+    // Stats for the current life, and the one that just ended
+    pub run: Stats,
+    pub last_run: Stats,
+    pub new_game_plus: usize,
+    pub is_endless: bool,
+    pub difficulty: Difficulty,
+    pub particles: Particles,
+    pub camera: CameraEffects,
 
     player: Player,
+    ability: Ability,
     retreat_button: Button,
-
-    // (Character Name, Price)
-    characters: [(String, usize); 4],
+    hp_text: Text,
+    magazine_text: Text,
+    wave_text: Text,
 
     // There can be multiple bullets/enemies in the game, so to keep track of them, they go into a vector
     bullets: Vec<Bullet>,
     enemies: Vec<Enemy>,
+    boomerang: Option<Boomerang>,
+    pickups: Vec<Pickup>,
+    power_ups: Vec<PowerUp>,
+    buffs: Buffs,
+    boss: Option<Boss>,
+    boss_bullets: Vec<Bullet>,
+    // Every map, and the obstacles left on the field from the one being played
+    maps: Vec<Map>,
+    map: usize,
+    obstacles: Vec<Obstacle>,
+    floating_texts: FloatingTexts,
+    // Enemies that are playing their death animation
+    enemy_deaths: Vec<Effect>,
+    enemy_death_animation: AnimationData,
 
     enemy_spawn_timer: f32,
+    power_up_spawn_timer: f32,
+    // Seconds since the battle started
+    battle_time: f32,
+    seed: u64,
+    is_run_started: bool,
+    // Frame time for everything on the field, stops during a hit-stop
+    dt: f32,
+    // Tier the battle was last played with, to notice upgrades
+    last_tier: usize,
 }
 
 impl Battling {
@@ -44,11 +127,26 @@ impl Battling {
             money: Money::new().await,
             bullet_count: Bullets::new().await,
             character: Character::new().await,
+            upgrades: Upgrades::default(),
             gun: Gun::new().await,
 
-            out_of_bullets: false,
+            death_report: DeathReport::default(),
+            next_seed: None,
+
+            is_boss_fight: false,
+            bosses_defeated: 0,
+
+            stat_events: Vec::new(),
+            run: Stats::default(),
+            last_run: Stats::default(),
+            new_game_plus: 0,
+            is_endless: false,
+            difficulty: Difficulty::default(),
+            particles: Particles::default(),
+            camera: CameraEffects::default(),
 
             player: Player::new().await,
+            ability: Ability::new(AbilityKind::None),
             retreat_button: Button::new(
                 vec2(screen_width() - 220., 20.),
                 vec2(200., 50.),
@@ -57,30 +155,96 @@ impl Battling {
                 15,
             )
             .await,
-
-            characters: [
-                ("Orange".to_string(), 100),
-                ("Pear".to_string(), 500),
-                ("Pineapple".to_string(), 1000),
-                ("Banana".to_string(), 10000),
-            ],
+            hp_text: Text::new(
+                Anchor::TopLeft,
+                vec2(0., 35.),
+                "res/Roboto-Medium.ttf".to_string(),
+                "HP: 1/1".to_string(),
+                30,
+                BLACK,
+            )
+            .await,
+            magazine_text: Text::new(
+                Anchor::TopLeft,
+                vec2(0., 70.),
+                "res/Roboto-Medium.ttf".to_string(),
+                "Magazine: 0/0".to_string(),
+                30,
+                BLACK,
+            )
+            .await,
+            wave_text: Text::new(
+                Anchor::TopLeft,
+                vec2(0., 105.),
+                "res/Roboto-Medium.ttf".to_string(),
+                "Wave 1".to_string(),
+                30,
+                BLACK,
+            )
+            .await,
 
             bullets: Vec::new(),
             enemies: Vec::new(),
+            boomerang: None,
+            pickups: Vec::new(),
+            power_ups: Vec::new(),
+            buffs: Buffs::default(),
+            boss: None,
+            boss_bullets: Vec::new(),
+            maps: obstacle::load_maps().await,
+            map: 0,
+            obstacles: Vec::new(),
+            floating_texts: FloatingTexts::new().await,
+            enemy_deaths: Vec::new(),
+            enemy_death_animation: AnimationData::load("res/animations/enemy_death.txt").await,
 
             enemy_spawn_timer: ENEMY_SPAWN_TIME,
+            power_up_spawn_timer: POWER_UP_SPAWN_TIME.0,
+            battle_time: 0.,
+            seed: 0,
+            is_run_started: false,
+            dt: 0.,
+            last_tier: 0,
         };
     }
 
     pub async fn start(&mut self) {
-        self.gun.bullets = self.bullet_count.bullets;
+        self.dt = self.camera.update(get_frame_time());
+        self.battle_time += self.dt;
+
+        // The field is drawn through the camera effects
+        self.camera.set();
+
+        if !self.is_run_started {
+            self.start_run();
+        }
+
+        // Obstacles are on the ground, under everything else
+        for obstacle in &self.obstacles {
+            obstacle.draw();
+        }
+
+        // Apply the stats of the current character
+        self.set_tier_stats();
+
+        // Aim at the mouse through the camera effects
+        self.gun.target = self.camera.mouse_position();
+
+        // Face where the gun is aiming
+        self.player.is_facing_left = self.gun.is_aiming_left();
 
         self.player.draw().await;
-        self.player.update(get_frame_time());
+        self.player.update(self.dt);
 
         self.gun.draw();
-        self.gun
-            .update(self.player.rect.point() + self.player.rect.size() * 0.5);
+        self.gun.update(
+            self.player.rect.point() + self.player.rect.size() * 0.5,
+            self.dt,
+            &mut self.bullet_count.bullets,
+        );
+
+        // Check if you need to reload the gun
+        self.check_reload();
 
         // Check if you need to reset the field/game
         self.check_reset().await;
@@ -88,50 +252,171 @@ impl Battling {
         // Check if you need to shoot a new bullet
         self.check_shoot_bullet().await;
 
+        // Check if you need to use the character's ability
+        self.check_use_ability().await;
+
         // Update/Draw all the bullets
         self.update_bullets();
 
+        // Update/Draw the boomerang
+        self.update_boomerang();
+
         // Check if you need to spawn enemy
         self.check_spawn_enemy().await;
 
         // Update/Draw all the enemies
         self.update_enemies();
 
+        // Update/Draw the boss and its bullets
+        self.update_boss().await;
+
+        // Keep everything out of the obstacles and break the ones that are shot
+        self.update_obstacles().await;
+
         // Check for a collision between a bullet and an enemy
         self.check_bullet_and_enemy_collision().await;
 
+        // Update/Draw/Collect all the pickups
+        self.update_pickups().await;
+
+        // Check if you need to spawn a power-up
+        self.check_spawn_power_up().await;
+
+        // Update/Draw/Collect all the power-ups
+        self.update_power_ups().await;
+
+        // Particles go over everything on the field
+        self.particles.update(self.dt);
+        self.particles.draw();
+
+        self.floating_texts.update(self.dt);
+        self.floating_texts.draw();
+
         // Set the player character
         self.set_player_character().await;
 
         // Set the enemy character
         self.set_enemy_character().await;
 
+        self.draw_shield();
+
+        // The HUD stays still
+        set_default_camera();
+
+        if let Some(boss) = &self.boss {
+            boss.draw_health_bar(self.hp_text.font);
+        }
+
         self.money.draw();
         self.bullet_count.draw();
 
+        self.hp_text
+            .change(format!("HP: {}/{}", self.player.hp, self.player.max_hp));
+        self.hp_text.draw();
+
+        self.magazine_text.change(if self.gun.is_reloading() {
+            "Reloading...".to_string()
+        } else {
+            format!("Magazine: {}/{}", self.gun.magazine, self.gun.magazine_size)
+        });
+        self.magazine_text.draw();
+
+        self.wave_text
+            .change(format!("Wave {}", leaderboard::wave(self.run.time_played)));
+        self.wave_text.draw();
+
+        self.ability.draw(self.hp_text.font);
+        self.buffs.draw(self.hp_text.font);
+
         self.retreat_button.draw().await;
-        self.retreat_button.update(
-            vec2(screen_width() - 220., self.retreat_button.rect.y),
-            self.retreat_button.rect.size(),
-        );
+        self.retreat_button
+            .set_rect(layout::place(Anchor::TopRight, BUTTON_SIZE, Vec2::ZERO));
+
+        // Record this frame into the current run
+        self.run.time_played += get_frame_time();
+
+        for event in &self.stat_events {
+            self.run.record(*event);
+        }
+
+        // Dying ends the run
+        if self.stat_events.contains(&StatEvent::Death) {
+            self.last_run = std::mem::take(&mut self.run);
+            self.is_run_started = false;
+        }
+    }
+
+    fn start_run(&mut self) {
+        // Seed the random numbers so the run can be played again
+        self.seed = self
+            .next_seed
+            .take()
+            .unwrap_or_else(|| (macroquad::miniquad::date::now() * 1000.) as u64);
+
+        rand::srand(self.seed);
+
+        // The seed picks the map too
+        self.map = rand::gen_range(0, self.maps.len());
+        self.obstacles = obstacle::place_map(&self.maps[self.map]);
+
+        self.is_run_started = true;
     }
 
     async fn check_shoot_bullet(&mut self) {
         // Check if the left mouse button is pressed
-        match is_mouse_button_down(MouseButton::Left) && self.gun.bullets > 0 {
+        match is_mouse_button_down(MouseButton::Left)
+            && self.gun.magazine > 0
+            && !self.gun.is_reloading()
+        {
             true => {
                 // If the left mouse button is pressed, check if you can shoot a new bullet
-                if self.gun.can_shoot {
-                    self.gun.bullets -= 1;
-                    self.bullet_count.decrement();
+                if self.gun.can_shoot && self.gun.cooldown <= 0. {
+                    let tier = self.character.tier();
+
+                    self.gun.magazine -= 1;
+
+                    // Bullets come out of the end of the barrel, the way the gun is pointing
+                    let pos = self.gun.muzzle();
+                    let dir = self.gun.direction();
+                    let speed = tier.bullet_speed * self.upgrades.multiplier(Stat::BulletSpeed);
 
                     // Shoot a bullet
-                    self.bullets.push(
-                        Bullet::new(self.gun.rect.point() + self.gun.rect.size() * 0.5).await,
+                    self.bullets
+                        .push(Bullet::with_direction(pos, dir, speed, tier.damage).await);
+                    self.stat_events.push(StatEvent::ShotFired);
+                    self.gun.flash();
+                    self.particles.emit(
+                        &particles::MUZZLE_SMOKE,
+                        self.gun.muzzle(),
+                        self.gun.direction(),
                     );
 
-                    // Set the gun's can_shoot to false until you release the left mouse button
-                    self.gun.can_shoot = false;
+                    // Shoot two extra bullets at an angle
+                    if self.buffs.is_active(PowerUpKind::TripleShot) {
+                        for angle in [-TRIPLE_SHOT_SPREAD, TRIPLE_SHOT_SPREAD] {
+                            self.bullets.push(
+                                Bullet::with_direction(
+                                    pos,
+                                    vec2(
+                                        dir.x * angle.cos() - dir.y * angle.sin(),
+                                        dir.x * angle.sin() + dir.y * angle.cos(),
+                                    ),
+                                    speed,
+                                    tier.damage,
+                                )
+                                .await,
+                            );
+                            self.stat_events.push(StatEvent::ShotFired);
+                        }
+                    }
+
+                    // Wait for the character's fire rate before shooting again
+                    self.gun.cooldown =
+                        1. / (tier.fire_rate * self.upgrades.multiplier(Stat::FireRate));
+
+                    if self.buffs.is_active(PowerUpKind::RapidFire) {
+                        self.gun.cooldown /= 2.;
+                    }
 
                     // Play the gunshot sound
                     play_sound(
@@ -150,10 +435,136 @@ impl Battling {
         }
     }
 
+    fn check_reload(&mut self) {
+        self.gun.magazine_size =
+            (MAGAZINE_SIZE * self.upgrades.multiplier(Stat::MagazineCapacity)) as usize;
+
+        if self.gun.needs_load {
+            self.gun.load(&mut self.bullet_count.bullets);
+        }
+
+        // Reload with the R key or when the magazine is empty
+        if self.bullet_count.bullets > 0 && (is_key_pressed(KeyCode::R) || self.gun.magazine == 0) {
+            self.gun
+                .reload(RELOAD_TIME / self.upgrades.multiplier(Stat::ReloadSpeed));
+        }
+    }
+
+    fn set_tier_stats(&mut self) {
+        let tier = self.character.tier();
+
+        self.player.speed = tier.speed * self.upgrades.multiplier(Stat::MovementSpeed);
+        self.player.acceleration = tier.acceleration;
+        self.player.friction = tier.friction;
+
+        // Punch in on a newly upgraded character
+        let index = tier::index(&self.character.character);
+
+        if index > self.last_tier {
+            self.camera.zoom_punch();
+        }
+
+        self.last_tier = index;
+
+        // A new character starts with full health and its own ability
+        if self.ability.kind != tier.ability {
+            self.player.max_hp = tier.max_hp;
+            self.player.hp = tier.max_hp;
+
+            self.ability = Ability::new(tier.ability);
+        }
+    }
+
+    async fn check_use_ability(&mut self) {
+        self.ability.update(self.dt);
+
+        if !self.ability.trigger() {
+            return;
+        }
+
+        let tier = self.character.tier();
+        let center = self.player.rect.point() + self.player.rect.size() * 0.5;
+
+        match self.ability.kind {
+            AbilityKind::Dash => {
+                // Dash towards the mouse
+                self.player.dash(
+                    self.camera.mouse_position() - center,
+                    AbilityKind::Dash.duration(),
+                );
+            }
+            AbilityKind::SpikyNova => {
+                // Shoot a ring of free bullets around the player
+                for i in 0..NOVA_BULLETS {
+                    let angle = i as f32 / NOVA_BULLETS as f32 * std::f32::consts::TAU;
+
+                    self.bullets.push(
+                        Bullet::with_direction(
+                            center,
+                            vec2(angle.cos(), angle.sin()),
+                            tier.bullet_speed * self.upgrades.multiplier(Stat::BulletSpeed),
+                            tier.damage,
+                        )
+                        .await,
+                    );
+                    self.stat_events.push(StatEvent::ShotFired);
+                }
+            }
+            AbilityKind::BananaBoomerang => {
+                if self.boomerang.is_none() {
+                    let target = self.camera.mouse_position();
+
+                    self.boomerang = Some(Boomerang::new(center, target).await);
+                }
+            }
+            // The shield is checked while it is active
+            AbilityKind::ShieldBurst | AbilityKind::None => {}
+        }
+    }
+
+    fn is_shielded(&self) -> bool {
+        self.ability.kind == AbilityKind::ShieldBurst && self.ability.is_active()
+    }
+
+    fn draw_shield(&self) {
+        if self.is_shielded() {
+            let center = self.player.rect.point() + self.player.rect.size() * 0.5;
+
+            draw_circle(
+                center.x,
+                center.y,
+                SHIELD_RADIUS,
+                Color::new(0., 0.5, 1., 0.25),
+            );
+            draw_circle_lines(center.x, center.y, SHIELD_RADIUS, 3., BLUE);
+        }
+    }
+
+    fn update_boomerang(&mut self) {
+        if let Some(boomerang) = &mut self.boomerang {
+            boomerang.update(
+                self.player.rect.point() + self.player.rect.size() * 0.5,
+                self.dt,
+            );
+            boomerang.draw();
+
+            // The boomerang kills every enemy it touches
+            for enemy in &mut self.enemies {
+                if enemy.is_alive && boomerang.is_collision(enemy) {
+                    enemy.damage(enemy.health);
+                }
+            }
+
+            if boomerang.is_done {
+                self.boomerang = None;
+            }
+        }
+    }
+
     fn update_bullets(&mut self) {
         // Loop through all bullets and draw/update them
         for bullet in &mut self.bullets {
-            bullet.update(get_frame_time());
+            bullet.update(self.dt);
             bullet.draw();
         }
 
@@ -170,6 +581,11 @@ impl Battling {
     }
 
     async fn check_spawn_enemy(&mut self) {
+        // Bosses are fought alone
+        if self.is_boss_fight {
+            return;
+        }
+
         if self.enemy_spawn_timer > 0. {
             self.enemy_spawn_timer -= 1.;
         } else {
@@ -207,21 +623,92 @@ impl Battling {
             let pos = vec2(x, y);
 
             // Spawn enemy
-            self.enemies
-                .push(Enemy::new(pos, self.player.rect.point()).await);
+            self.enemies.push(
+                Enemy::new(
+                    pos,
+                    self.player.rect.point(),
+                    self.character.tier().enemy_health * self.scaling(),
+                    ENEMY_SPEED * self.difficulty.enemy_speed(),
+                )
+                .await,
+            );
 
             // Reset spawn timer
+            // Every wave spawns enemies a bit faster
             self.enemy_spawn_timer = ENEMY_SPAWN_TIME
+                / (1. + (leaderboard::wave(self.run.time_played) - 1) as f32 * WAVE_SPAWN_SCALING)
+                / self.difficulty.spawn_rate();
+
+            if self.is_endless {
+                // Enemies keep spawning faster the longer you survive
+                self.enemy_spawn_timer /= 1. + self.battle_time / ENDLESS_RAMP_TIME;
+            }
+        }
+    }
+
+    // Enemy health and money multiplier for New Game+
+    fn scaling(&self) -> f32 {
+        1. + self.new_game_plus as f32 * NEW_GAME_PLUS_SCALING
+    }
+
+    // Slow motion slows down everything that is trying to hurt the player
+    fn enemy_dt(&self) -> f32 {
+        if self.buffs.is_active(PowerUpKind::SlowMotion) {
+            self.dt * SLOW_MOTION_FACTOR
+        } else {
+            self.dt
         }
     }
 
     fn update_enemies(&mut self) {
+        let dt = self.enemy_dt();
+
+        let player_pos = self.player.rect.point() + self.player.rect.size() * 0.5;
+        // Enemies run away from an invincible player
+        let is_fleeing = self.player.is_invincible;
+
+        let obstacle_rects = self.obstacle_rects();
+
+        // Where every enemy was at the start of the frame, so they all steer around the same picture
+        let boids: Vec<Boid> = self
+            .enemies
+            .iter()
+            .filter(|enemy| enemy.is_alive)
+            .map(|enemy| Boid {
+                pos: enemy.center(),
+                vel: enemy.vel,
+            })
+            .collect();
+
         // Loop through all enemies and draw/update them
         for enemy in &mut self.enemies {
-            enemy.update(get_frame_time());
+            let boid = Boid {
+                pos: enemy.center(),
+                vel: enemy.vel,
+            };
+            let desired = steering::steer(
+                boid,
+                player_pos,
+                is_fleeing,
+                &boids,
+                &obstacle_rects,
+                enemy.speed,
+            );
+
+            enemy.steer(desired, dt);
+            enemy.update(dt);
             enemy.draw();
         }
 
+        // Finish the death animations of killed enemies
+        for death in &mut self.enemy_deaths {
+            death.update(dt);
+            death.draw();
+        }
+
+        self.enemy_deaths
+            .retain(|death| !death.animation.is_finished);
+
         for enemy in self.enemies.clone() {
             if enemy.rect.x > screen_width()
                 || enemy.rect.y > screen_height()
@@ -234,44 +721,429 @@ impl Battling {
         }
     }
 
+    async fn update_boss(&mut self) {
+        if self.is_boss_fight && self.boss.is_none() {
+            if let Some(def) = BOSSES.get(self.bosses_defeated) {
+                let mut boss = Boss::new(def).await;
+
+                boss.health *= self.scaling();
+                boss.max_health *= self.scaling();
+
+                self.boss = Some(boss);
+            }
+        }
+
+        let player_pos = self.player.rect.point() + self.player.rect.size() * 0.5;
+        let obstacle_rects = self.obstacle_rects();
+        let dt = self.enemy_dt();
+
+        if let Some(boss) = &mut self.boss {
+            let mut bullets = boss.update(player_pos, &obstacle_rects, dt).await;
+            self.boss_bullets.append(&mut bullets);
+
+            boss.draw();
+
+            // Check if the player's bullets hit the boss
+            let boss_rect = boss.rect;
+
+            for bullet in &self.bullets {
+                if bullet.rect.overlaps(&boss_rect) {
+                    boss.damage(bullet.damage);
+                    self.stat_events.push(StatEvent::Hit);
+
+                    self.floating_texts.spawn(
+                        format!("{}", bullet.damage),
+                        bullet.rect.point(),
+                        floating_text::DAMAGE_COLOR,
+                    );
+                }
+            }
+
+            self.bullets
+                .retain(|bullet| !bullet.rect.overlaps(&boss_rect));
+
+            // The boomerang hurts the boss for as long as it touches it
+            if let Some(boomerang) = &self.boomerang {
+                if boomerang.rect.overlaps(&boss_rect) {
+                    boss.damage(self.character.tier().damage * 5. * self.dt);
+                }
+            }
+        }
+
+        // Loop through all the boss bullets and draw/update them
+        for bullet in &mut self.boss_bullets {
+            bullet.update(dt);
+            bullet.draw();
+        }
+
+        self.boss_bullets.retain(|bullet| {
+            bullet.rect.x < screen_width()
+                && bullet.rect.y < screen_height()
+                && bullet.rect.x > 0.
+                && bullet.rect.y > 0.
+        });
+
+        self.check_boss_defeated().await;
+    }
+
+    fn obstacle_rects(&self) -> Vec<Rect> {
+        self.obstacles
+            .iter()
+            .map(|obstacle| obstacle.rect)
+            .collect()
+    }
+
+    async fn update_obstacles(&mut self) {
+        let obstacles = &mut self.obstacles;
+
+        // Bullets stop at obstacles, and the player's bullets break them
+        self.bullets.retain(|bullet| {
+            match obstacles
+                .iter_mut()
+                .find(|obstacle| obstacle.rect.overlaps(&bullet.rect))
+            {
+                Some(obstacle) => {
+                    obstacle.damage(bullet.damage);
+
+                    false
+                }
+                None => true,
+            }
+        });
+
+        self.boss_bullets.retain(|bullet| {
+            !obstacles
+                .iter()
+                .any(|obstacle| obstacle.rect.overlaps(&bullet.rect))
+        });
+
+        for obstacle in self
+            .obstacles
+            .iter()
+            .filter(|obstacle| obstacle.is_destroyed())
+        {
+            let center = obstacle.center();
+
+            match obstacle.kind {
+                ObstacleKind::Crate => {
+                    self.particles
+                        .emit(&particles::SPLINTERS, center, Vec2::ZERO);
+
+                    // Crates have a few bullets inside
+                    self.pickups
+                        .push(Pickup::new(center, PickupKind::Ammo(rand::gen_range(2, 6))).await);
+                }
+                ObstacleKind::FruitBowl => {
+                    self.particles.emit(&particles::JUICE, center, Vec2::ZERO);
+                }
+                ObstacleKind::TreeStump => {}
+            }
+
+            // Play the breaking sound
+            play_sound(
+                load_sound("res/audio/kill.wav").await.unwrap(),
+                PlaySoundParams {
+                    volume: 0.2,
+                    looped: false,
+                },
+            )
+        }
+
+        self.obstacles.retain(|obstacle| !obstacle.is_destroyed());
+
+        // Push the player, enemies and boss out of the obstacles
+        for rect in self.obstacle_rects() {
+            self.player
+                .push_out(obstacle::push_out(self.player.rect, rect));
+
+            for enemy in &mut self.enemies {
+                enemy.rect = enemy.rect.offset(obstacle::push_out(enemy.rect, rect));
+            }
+
+            if let Some(boss) = &mut self.boss {
+                boss.rect = boss.rect.offset(obstacle::push_out(boss.rect, rect));
+            }
+        }
+    }
+
+    async fn check_boss_defeated(&mut self) {
+        if let Some(boss) = &self.boss {
+            if boss.is_alive() {
+                return;
+            }
+
+            // Blow the boss up
+            self.particles.emit(
+                &particles::DEATH_EXPLOSION,
+                boss.rect.point() + boss.rect.size() * 0.5,
+                Vec2::ZERO,
+            );
+            self.camera.add_trauma(EXPLOSION_TRAUMA);
+
+            // Give the boss reward
+            self.money.increment(boss.reward_money);
+            self.floating_texts.spawn(
+                format!("+${}", boss.reward_money),
+                boss.rect.point() + boss.rect.size() * 0.5,
+                floating_text::MONEY_COLOR,
+            );
+            self.bullet_count.bullets += boss.reward_bullets;
+
+            self.stat_events.push(StatEvent::Kill);
+            self.stat_events.push(StatEvent::BossDefeated);
+            self.stat_events
+                .push(StatEvent::MoneyEarned(boss.reward_money));
+
+            self.bosses_defeated += 1;
+
+            self.boss = None;
+            self.boss_bullets.clear();
+            self.is_boss_fight = false;
+
+            // Play the enemy death sound
+            play_sound(
+                load_sound("res/audio/kill.wav").await.unwrap(),
+                PlaySoundParams {
+                    volume: 0.5,
+                    looped: false,
+                },
+            )
+        }
+    }
+
     async fn check_bullet_and_enemy_collision(&mut self) {
-        for enemy in self.enemies.clone() {
-            for bullet in self.bullets.clone() {
-                // Check if bullet collides with enemy
-                if bullet.is_collision(enemy.clone())
-                    && self.enemies.iter().position(|r| *r == enemy) != None
-                {
-                    // Remove enemy
-                    self.enemies
-                        .remove(self.enemies.iter().position(|r| *r == enemy).unwrap());
-                    self.money.increment(rand::gen_range(
-                        self.characters[self
-                            .characters
-                            .iter()
-                            .position(|r| *r.0 == self.character.character)
-                            .unwrap_or_default()]
-                        .1 / 100,
-                        self.characters[self
-                            .characters
-                            .iter()
-                            .position(|r| *r.0 == self.character.character)
-                            .unwrap_or_default()]
-                        .1 / 10,
-                    ));
-
-                    // Play the enemy death sound
-                    play_sound(
-                        load_sound("res/audio/kill.wav").await.unwrap(),
-                        PlaySoundParams {
-                            volume: 0.3,
-                            looped: false,
-                        },
+        let mut hit_bullets = Vec::new();
+
+        for (i, bullet) in self.bullets.iter().enumerate() {
+            // Check if bullet collides with enemy
+            if let Some(enemy) = self
+                .enemies
+                .iter_mut()
+                .find(|enemy| enemy.is_alive && bullet.is_collision((*enemy).clone()))
+            {
+                enemy.damage(bullet.damage);
+
+                // Only enemies that take more than one hit show the damage
+                if enemy.max_health > bullet.damage {
+                    self.floating_texts.spawn(
+                        format!("{}", bullet.damage),
+                        enemy.rect.point() + enemy.rect.size() * 0.5,
+                        floating_text::DAMAGE_COLOR,
+                    );
+                }
+
+                hit_bullets.push(i);
+                self.stat_events.push(StatEvent::Hit);
+            }
+        }
+
+        // Remove the bullets that hit an enemy
+        self.bullets = self
+            .bullets
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !hit_bullets.contains(i))
+            .map(|(_, bullet)| *bullet)
+            .collect();
+
+        self.reward_dead_enemies().await;
+    }
+
+    async fn reward_dead_enemies(&mut self) {
+        let reward = self.character.tier().reward;
+        let mut multiplier = self.upgrades.multiplier(Stat::MoneyMultiplier) * self.scaling();
+
+        if self.buffs.is_active(PowerUpKind::DoubleMoney) {
+            multiplier *= 2.;
+        }
+
+        for enemy in &mut self.enemies {
+            if !enemy.is_alive && !enemy.has_given_money {
+                let pos = enemy.rect.point() + enemy.rect.size() * 0.5;
+
+                // Splatter the fruit's juice
+                self.particles.emit(
+                    &particles::Emitter {
+                        start_color: self.character.tier().enemy_juice,
+                        ..particles::JUICE
+                    },
+                    pos,
+                    Vec2::ZERO,
+                );
+
+                // Drop money where the enemy died
+                self.pickups.push(
+                    Pickup::new(
+                        pos,
+                        PickupKind::Coin(
+                            (rand::gen_range(reward.0, reward.1) as f32 * multiplier) as usize,
+                        ),
                     )
+                    .await,
+                );
+
+                // Sometimes drop an ammo crate too
+                if rand::gen_range(0., 1.) < AMMO_DROP_CHANCE {
+                    self.pickups.push(
+                        Pickup::new(
+                            pos + vec2(rand::gen_range(-20., 20.), rand::gen_range(-20., 20.)),
+                            PickupKind::Ammo(rand::gen_range(3, 8)),
+                        )
+                        .await,
+                    );
+                }
+
+                enemy.has_given_money = true;
+                self.stat_events.push(StatEvent::Kill);
+
+                self.camera.hit_stop(KILL_HIT_STOP);
+                self.camera.add_trauma(KILL_TRAUMA);
+
+                // Play the enemy death sound
+                play_sound(
+                    load_sound("res/audio/kill.wav").await.unwrap(),
+                    PlaySoundParams {
+                        volume: 0.3,
+                        looped: false,
+                    },
+                )
+            }
+        }
+
+        // Dead enemies play their death animation before they are removed
+        for enemy in &self.enemies {
+            if !enemy.is_alive {
+                self.enemy_deaths.push(Effect::new(
+                    enemy.rect,
+                    enemy.texture,
+                    &self.enemy_death_animation,
+                ));
+            }
+        }
+
+        // Remove dead enemies
+        self.enemies.retain(|enemy| enemy.is_alive);
+    }
+
+    async fn update_pickups(&mut self) {
+        let player_pos = self.player.rect.point() + self.player.rect.size() * 0.5;
+        let magnet_radius = MAGNET_RADIUS * self.upgrades.multiplier(Stat::PickupMagnet);
+
+        for pickup in &mut self.pickups {
+            pickup.update(player_pos, magnet_radius, self.dt);
+            pickup.draw();
+
+            // Check if the player walked over the pickup
+            if pickup.rect.overlaps(&self.player.rect) {
+                match pickup.kind {
+                    PickupKind::Coin(amount) => {
+                        self.particles.emit(
+                            &particles::COIN_SPARKLE,
+                            pickup.rect.point() + pickup.rect.size() * 0.5,
+                            Vec2::ZERO,
+                        );
+
+                        self.money.increment(amount);
+                        self.stat_events.push(StatEvent::MoneyEarned(amount));
+
+                        self.floating_texts.spawn(
+                            format!("+${}", amount),
+                            pickup.rect.point(),
+                            floating_text::MONEY_COLOR,
+                        );
+                    }
+                    PickupKind::Ammo(amount) => {
+                        self.bullet_count.bullets += amount;
+
+                        self.floating_texts.spawn(
+                            format!("+{} bullets", amount),
+                            pickup.rect.point(),
+                            floating_text::AMMO_COLOR,
+                        );
+                    }
                 }
+
+                pickup.is_collected = true;
+
+                // Play the pickup sound
+                play_sound(
+                    load_sound("res/audio/click.wav").await.unwrap(),
+                    PlaySoundParams {
+                        volume: 0.3,
+                        looped: false,
+                    },
+                )
+            }
+        }
+
+        // Remove collected and expired pickups
+        self.pickups
+            .retain(|pickup| !pickup.is_collected && !pickup.is_expired());
+    }
+
+    async fn check_spawn_power_up(&mut self) {
+        self.power_up_spawn_timer -= self.dt;
+
+        if self.power_up_spawn_timer <= 0. {
+            let kind = PowerUpKind::ALL[rand::gen_range(0, PowerUpKind::ALL.len())];
+
+            // Spawn the power-up somewhere inside the field, but not inside an obstacle
+            // If every try lands in one the field is too full, so skip this power-up
+            let pos = (0..POWER_UP_SPAWN_TRIES)
+                .map(|_| {
+                    vec2(
+                        rand::gen_range(50., screen_width() - 50.),
+                        rand::gen_range(50., screen_height() - 50.),
+                    )
+                })
+                .find(|pos| {
+                    !self
+                        .obstacles
+                        .iter()
+                        .any(|obstacle| obstacle.rect.contains(*pos))
+                });
+
+            if let Some(pos) = pos {
+                self.power_ups.push(PowerUp::new(pos, kind).await);
             }
+
+            // Reset spawn timer
+            self.power_up_spawn_timer =
+                rand::gen_range(POWER_UP_SPAWN_TIME.0, POWER_UP_SPAWN_TIME.1);
         }
     }
 
+    async fn update_power_ups(&mut self) {
+        self.buffs.update(self.dt);
+
+        for power_up in &mut self.power_ups {
+            power_up.update(self.dt);
+            power_up.draw();
+
+            // Check if the player walked over the power-up
+            if power_up.rect.overlaps(&self.player.rect) {
+                self.buffs.add(power_up.kind);
+
+                power_up.is_collected = true;
+
+                // Play the pickup sound
+                play_sound(
+                    load_sound("res/audio/click.wav").await.unwrap(),
+                    PlaySoundParams {
+                        volume: 0.3,
+                        looped: false,
+                    },
+                )
+            }
+        }
+
+        // Remove collected and expired power-ups
+        self.power_ups
+            .retain(|power_up| !power_up.is_collected && !power_up.is_expired());
+
+        self.player.is_invincible = self.buffs.is_active(PowerUpKind::Invincibility);
+    }
+
     async fn set_player_character(&mut self) {
         self.player.texture =
             "res/regular/".to_string() + self.character.character.to_lowercase().as_str() + ".png";
@@ -279,88 +1151,165 @@ impl Battling {
 
     async fn set_enemy_character(&mut self) {
         for enemy in &mut self.enemies {
-            enemy.texture = if self.character.character == "Apple".to_string() {
-                load_texture("res/enemy/orange.png").await.unwrap()
-            } else if self.character.character != "Banana".to_string() {
-                load_texture(
-                    ("res/enemy/".to_string()
-                        + self.characters[self
-                            .characters
-                            .iter()
-                            .position(|r| *r.0 == self.character.character)
-                            .unwrap_or_default()
-                            + 1]
-                        .0
-                        .to_lowercase()
-                        .as_str()
-                        + ".png")
-                        .as_str(),
-                )
-                .await
-                .unwrap()
-            } else {
-                load_texture("res/monkey.png").await.unwrap()
-            };
+            enemy.texture = load_texture(self.character.tier().enemy).await.unwrap();
         }
     }
 
     async fn check_reset(&mut self) {
-        for enemy in self.enemies.clone() {
-            if self.player.is_collision(enemy) {
-                self.player.reset();
-                self.gun
-                    .reset(self.player.rect.point() + self.player.rect.size());
+        let is_shielded = self.is_shielded() || self.player.is_invincible;
+        let mut is_hit = false;
+        // What hit the player
+        let mut cause = String::new();
 
-                self.enemies.clear();
-                self.bullets.clear();
+        for enemy in &mut self.enemies {
+            if !enemy.is_alive || !self.player.is_collision(enemy.clone()) {
+                continue;
+            }
 
-                self.money.reset();
-                self.bullet_count.reset();
+            if is_shielded {
+                // The shield and invincibility kill enemies that touch them
+                enemy.damage(enemy.health);
+            } else if !self.player.is_invulnerable() {
+                // The enemy is used up by hitting the player, so it gives no money
+                enemy.is_alive = false;
+                enemy.has_given_money = true;
+
+                // It blows up in the player's face
+                self.particles.emit(
+                    &particles::DEATH_EXPLOSION,
+                    enemy.rect.point() + enemy.rect.size() * 0.5,
+                    Vec2::ZERO,
+                );
+
+                is_hit = true;
+                cause = self.character.tier().enemy_name.to_string();
+            }
+        }
 
-                self.game_state = GameState::Dead;
+        // Check if the boss or its bullets hit the player
+        if let Some(boss) = &self.boss {
+            if boss.rect.overlaps(&self.player.rect) && !is_shielded {
+                is_hit = true;
+                cause = boss.name.to_string();
+            }
+        }
 
-                self.gun.can_shoot = false;
+        let player_rect = self.player.rect;
+        let boss_bullet_count = self.boss_bullets.len();
 
-                // Play the death sound
-                play_sound(
-                    load_sound("res/audio/die.wav").await.unwrap(),
-                    PlaySoundParams {
-                        volume: 0.5,
-                        looped: false,
-                    },
-                )
+        self.boss_bullets
+            .retain(|bullet| !bullet.rect.overlaps(&player_rect));
+
+        if self.boss_bullets.len() < boss_bullet_count && !is_shielded {
+            is_hit = true;
+
+            if let Some(boss) = &self.boss {
+                cause = format!("{}'s bullet", boss.name);
             }
         }
 
-        if self.retreat_button.is_pressed() {
-            self.player.reset();
-            self.gun
-                .reset(self.player.rect.point() + self.player.rect.size());
+        if is_hit && !self.player.is_invulnerable() {
+            self.camera.add_trauma(PLAYER_HIT_TRAUMA);
+        }
+
+        if is_hit && !self.player.is_invulnerable() && self.player.hit() {
+            self.die(DeathCause::Killed(cause));
 
-            self.enemies.clear();
-            self.bullets.clear();
+            // Play the death sound
+            play_sound(
+                load_sound("res/audio/die.wav").await.unwrap(),
+                PlaySoundParams {
+                    volume: 0.5,
+                    looped: false,
+                },
+            )
+        }
+
+        if self.retreat_button.is_clicked() {
+            // Keep the bullets that were still in the magazine
+            self.gun.unload(&mut self.bullet_count.bullets);
+
+            self.clear_field();
+
+            self.stat_events.push(StatEvent::Retreat);
 
             self.game_state = GameState::Upgrading;
+        }
 
-            self.gun.can_shoot = false;
+        if self.gun.magazine == 0
+            && self.bullet_count.bullets == 0
+            && self.money.money == 0
+            && self.bullets.is_empty()
+            // Dropped money or ammo can still save you
+            && self.pickups.is_empty()
+        {
+            self.die(DeathCause::OutOfBullets);
         }
+    }
 
-        if self.gun.bullets <= 0 && self.money.money <= 0 && self.bullets.is_empty() {
-            self.player.reset();
-            self.gun
-                .reset(self.player.rect.point() + self.player.rect.size());
+    fn die(&mut self, cause: DeathCause) {
+        // The magazine is lost along with the rest of the bullets
+        self.gun.unload(&mut self.bullet_count.bullets);
+
+        // Lose part of what you were carrying depending on the difficulty
+        let penalty = self.difficulty.death_penalty();
+        let money_lost = (self.money.money as f32 * penalty).ceil() as usize;
+        let bullets_lost = (self.bullet_count.bullets as f32 * penalty).ceil() as usize;
 
-            self.enemies.clear();
-            self.bullets.clear();
+        self.money.money -= money_lost;
+        self.bullet_count.bullets -= bullets_lost;
 
-            self.money.reset();
+        // Always leave enough to keep playing
+        if self.money.money == 0 && self.bullet_count.bullets == 0 {
             self.bullet_count.reset();
+        }
+
+        self.death_report = DeathReport {
+            cause,
+            difficulty: self.difficulty,
+            money_lost,
+            bullets_lost,
+            seed: self.seed,
+        };
 
-            self.game_state = GameState::Dead;
+        self.clear_field();
 
-            self.gun.can_shoot = false;
+        self.stat_events.push(StatEvent::Death);
 
-            self.out_of_bullets = true;
+        if self.difficulty.is_permadeath() {
+            self.stat_events.push(StatEvent::HardcoreDeath);
         }
+
+        self.game_state = GameState::Dead;
+    }
+
+    fn clear_field(&mut self) {
+        self.battle_time = 0.;
+
+        self.player.reset();
+        self.gun
+            .reset(self.player.rect.point() + self.player.rect.size() * 0.5);
+
+        // Broken obstacles are put back
+        self.obstacles = obstacle::place_map(&self.maps[self.map]);
+
+        self.enemies.clear();
+        self.enemy_deaths.clear();
+        self.particles.clear();
+        self.floating_texts.clear();
+        self.camera.reset();
+        self.bullets.clear();
+        self.boomerang = None;
+        self.pickups.clear();
+        self.power_ups.clear();
+        self.buffs.reset();
+        self.ability.reset();
+
+        // Leaving the field gives up the boss fight
+        self.boss = None;
+        self.boss_bullets.clear();
+        self.is_boss_fight = false;
+
+        self.gun.can_shoot = false;
     }
 }
@@ -1,102 +1,247 @@
 use crate::objects::button::Button;
+use crate::objects::difficulty::Difficulty;
+use crate::objects::focus::Focus;
+use crate::objects::layout::{self, Anchor, Stack, BUTTON_SIZE};
+use crate::objects::leaderboard::{self, Entry, Leaderboard};
+use crate::objects::stats::{StatEvent, Stats};
+use crate::objects::text_input::TextInput;
 use crate::states::game_state::GameState;
 
 use macroquad::prelude::*;
 
+const NAME_INPUT_SIZE: Vec2 = const_vec2!([300., 40.]);
+
+#[derive(Clone, Default)]
+pub enum DeathCause {
+    // What hit the player, like an enemy or the boss
+    Killed(String),
+    #[default]
+    OutOfBullets,
+}
+
+impl DeathCause {
+    pub fn title(&self) -> &str {
+        match self {
+            Self::Killed(_) => "You died",
+            Self::OutOfBullets => "You ran out of bullets",
+        }
+    }
+
+    // The first line of the summary
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Killed(killer) => format!("Killed by: {}", killer),
+            Self::OutOfBullets => "Ran out of bullets".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct DeathReport {
+    pub cause: DeathCause,
+    pub difficulty: Difficulty,
+    pub money_lost: usize,
+    pub bullets_lost: usize,
+    pub seed: u64,
+}
+
 pub struct Dead {
     pub game_state: GameState,
-    pub out_of_bullets: bool,
+    pub report: DeathReport,
+    // Set when the player wants to play the same run again
+    pub retry_seed: Option<u64>,
+
+    // The run that just ended
+    pub run: Stats,
+    // Set when a new run has ended and can be put on the leaderboard
+    pub is_new_run: bool,
+    pub leaderboard: Leaderboard,
+    // Things that happened this frame, for the achievements to record
+    pub stat_events: Vec<StatEvent>,
 
     title: String,
     options: Vec<Button>,
+    name_input: TextInput,
+    is_entering_name: bool,
+    // Where the last run was placed on the leaderboard
+    highlight: Option<usize>,
 
-    can_press_options: bool,
+    focus: Focus,
 }
 
 impl Dead {
     pub async fn new() -> Self {
         return Self {
             game_state: GameState::Dead,
-            out_of_bullets: false,
+            report: DeathReport::default(),
+            retry_seed: None,
+
+            run: Stats::default(),
+            is_new_run: false,
+            leaderboard: Leaderboard::load(),
+            stat_events: Vec::new(),
 
             title: "You Died".to_string(),
             options: vec![
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Try Again".to_string(), RED, 14).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Menu".to_string(), RED, 15).await,
                 Button::new(
-                    vec2(screen_width() / 2. - 100., screen_height() / 2. - 200.),
-                    vec2(200., 50.),
-                    "Menu".to_string(),
-                    RED,
-                    15,
-                )
-                .await,
-                Button::new(
-                    vec2(screen_width() - 220., 20.),
-                    vec2(200., 50.),
-                    "Try Again".to_string(),
+                    Vec2::ZERO,
+                    BUTTON_SIZE,
+                    "Retry Same Seed".to_string(),
                     RED,
                     14,
                 )
                 .await,
             ],
+            name_input: TextInput::new(Vec2::ZERO, NAME_INPUT_SIZE * layout::scale(), 16).await,
+            is_entering_name: false,
+            highlight: None,
 
-            can_press_options: false,
+            focus: Focus::default(),
         };
     }
 
-    pub async fn start(&mut self) {
-        if !is_mouse_button_pressed(MouseButton::Left) {
-            self.can_press_options = true;
+    fn check_leaderboard(&mut self, font: Font) {
+        let score = leaderboard::score(&self.run);
+
+        if self.is_new_run {
+            self.is_new_run = false;
+            self.highlight = None;
+
+            // Ask for a name if the run made it onto the leaderboard
+            self.is_entering_name = self.leaderboard.qualifies(score);
+            self.name_input.clear();
+        }
+
+        if self.is_entering_name {
+            layout::draw_text_anchored(
+                format!("New high score: {}! Type your name and press Enter", score).as_str(),
+                Anchor::Top,
+                vec2(0., 45.),
+                font,
+                20,
+                BLACK,
+            );
+
+            let rect = layout::place(Anchor::Top, NAME_INPUT_SIZE, vec2(0., 70.));
+
+            self.name_input.rect = rect;
+            self.name_input.update(rect.point());
+            self.name_input.draw();
+
+            if is_key_pressed(KeyCode::Enter) {
+                let name = match self.name_input.text.trim() {
+                    "" => "Player".to_string(),
+                    name => name.to_string(),
+                };
+
+                self.highlight = Some(self.leaderboard.add(Entry::new(name, &self.run)));
+                self.stat_events.push(StatEvent::HighScore);
+                self.is_entering_name = false;
+            }
+        } else {
+            layout::draw_text_anchored(
+                format!("Score: {}", score).as_str(),
+                Anchor::Top,
+                vec2(0., 45.),
+                font,
+                20,
+                BLACK,
+            );
+        }
+
+        // Draw the top of the leaderboard under the buttons
+        self.leaderboard.draw(
+            layout::place(Anchor::Bottom, leaderboard::table_size(5), Vec2::ZERO),
+            5,
+            self.highlight,
+            font,
+        );
+    }
+
+    fn draw_summary(&self, font: Font) {
+        let lines = [
+            self.report.cause.describe(),
+            format!(
+                "Survived: {}:{:02}",
+                self.run.time_played as usize / 60,
+                self.run.time_played as usize % 60
+            ),
+            format!("Kills: {}", self.run.kills),
+            format!("Accuracy: {:.1}%", self.run.accuracy()),
+            format!("Money gained: ${}", self.run.money_earned),
+            format!("Money lost: ${}", self.report.money_lost),
+            format!("Bullets lost: {}", self.report.bullets_lost),
+            format!("Seed: {}", self.report.seed),
+            format!("Difficulty: {}", self.report.difficulty.name()),
+        ];
+
+        // Draw the summary on the left of the buttons
+        layout::draw_lines(
+            &lines,
+            Anchor::TopLeft,
+            vec2(0., 120.),
+            25.,
+            font,
+            18,
+            BLACK,
+        );
+
+        if self.report.difficulty.is_permadeath() {
+            layout::draw_text_anchored(
+                "Permadeath: you are back to being an apple",
+                Anchor::TopLeft,
+                vec2(0., 120. + lines.len() as f32 * 25.),
+                font,
+                18,
+                RED,
+            );
         }
+    }
+
+    pub async fn start(&mut self) {
+        let font = load_ttf_font("res/Roboto-Medium.ttf").await.unwrap();
+
+        self.check_leaderboard(font);
+        self.draw_summary(font);
 
         // Draw title
-        draw_text_ex(
-            if self.out_of_bullets {
-                self.title = "You ran out of bullets".to_string();
-
-                self.title.as_str()
-            } else {
-                self.title = "You died".to_string();
-
-                self.title.as_str()
-            },
-            screen_width() * 0.5 - 7. - self.title.to_string().chars().count() as f32 * 7.,
-            30.,
-            TextParams {
-                font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
-                font_size: 30,
-                color: BLACK,
-                font_scale: 1.,
-                font_scale_aspect: 1.,
-            },
+        self.title = self.report.cause.title().to_string();
+
+        layout::draw_text_anchored(
+            self.title.as_str(),
+            Anchor::Top,
+            Vec2::ZERO,
+            font,
+            30,
+            BLACK,
         );
 
+        let column = Stack::column(Anchor::Center, BUTTON_SIZE, self.options.len());
+
+        // Enter is for typing the name until it is submitted
+        if self.is_entering_name {
+            self.focus = Focus::default();
+        }
+
+        self.focus.update_buttons(&mut self.options);
+
         // Draw buttons
-        for option in &mut self.options {
+        for (i, option) in self.options.iter_mut().enumerate() {
+            option.set_rect(column.rect(i));
             option.draw().await;
 
-            // Update button position
+            if !option.is_clicked() {
+                continue;
+            }
+
             match option.text.as_str() {
-                "Try Again" => {
-                    option.update(
-                        vec2(screen_width() / 2. - 100., screen_height() / 2. - 55.),
-                        vec2(200., 50.),
-                    );
-
-                    if option.is_pressed() && self.can_press_options {
-                        self.game_state = GameState::Battling;
-                        self.can_press_options = false;
-                    }
-                }
-                "Menu" => {
-                    option.update(
-                        vec2(screen_width() / 2. - 100., screen_height() / 2. + 5.),
-                        vec2(200., 50.),
-                    );
-
-                    if option.is_pressed() && self.can_press_options {
-                        self.game_state = GameState::Menu;
-                        self.can_press_options = false;
-                    }
+                "Try Again" => self.game_state = GameState::Battling,
+                "Menu" => self.game_state = GameState::Menu,
+                "Retry Same Seed" => {
+                    self.retry_seed = Some(self.report.seed);
+                    self.game_state = GameState::Battling;
                 }
                 _ => {}
             }
@@ -5,4 +5,9 @@ pub enum GameState {
     Battling,
     Upgrading,
     Tutorial,
+    Victory,
+    Statistics,
+    HighScores,
+    Achievements,
+    Options,
 }
@@ -0,0 +1,68 @@
+use crate::objects::button::Button;
+use crate::objects::layout::{self, Anchor, BUTTON_SIZE};
+use crate::objects::leaderboard::{self, Leaderboard};
+use crate::states::game_state::GameState;
+
+use macroquad::prelude::*;
+
+const ROWS: usize = 10;
+
+pub struct HighScores {
+    pub game_state: GameState,
+    pub leaderboard: Leaderboard,
+
+    title: String,
+    back_button: Button,
+    font: Font,
+}
+
+impl HighScores {
+    pub async fn new() -> Self {
+        Self {
+            game_state: GameState::HighScores,
+            leaderboard: Leaderboard::default(),
+
+            title: "Leaderboard".to_string(),
+            back_button: Button::new(Vec2::ZERO, BUTTON_SIZE, "Back".to_string(), RED, 15).await,
+            font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
+        }
+    }
+
+    pub async fn start(&mut self) {
+        let font = self.font;
+
+        // Draw title
+        layout::draw_text_anchored(
+            self.title.as_str(),
+            Anchor::Top,
+            Vec2::ZERO,
+            font,
+            30,
+            BLACK,
+        );
+
+        // The table goes under the title
+        let table = layout::place(Anchor::Top, leaderboard::table_size(ROWS), vec2(0., 60.));
+
+        if self.leaderboard.entries.is_empty() && !self.leaderboard.was_tampered {
+            layout::draw_text_anchored(
+                "No scores yet, go and play!",
+                Anchor::Top,
+                vec2(0., 120.),
+                font,
+                20,
+                BLACK,
+            );
+        }
+
+        self.leaderboard.draw(table, ROWS, None, font);
+
+        self.back_button
+            .set_rect(layout::place(Anchor::TopLeft, BUTTON_SIZE, Vec2::ZERO));
+        self.back_button.draw().await;
+
+        if self.back_button.is_clicked() {
+            self.game_state = GameState::Menu;
+        }
+    }
+}
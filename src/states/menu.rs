@@ -1,4 +1,7 @@
 use crate::objects::button::Button;
+use crate::objects::difficulty::Difficulty;
+use crate::objects::focus::Focus;
+use crate::objects::layout::{self, Anchor, Stack, BUTTON_SIZE};
 use crate::states::game_state::GameState;
 
 use macroquad::prelude::*;
@@ -8,7 +11,16 @@ pub struct Menu {
     pub options: Vec<Button>,
     pub game_state: GameState,
 
-    can_press_options: bool,
+    // Set when a difficulty was picked and a new game should start
+    pub start_new_game: bool,
+    pub difficulty: Difficulty,
+    // Set when there is a game in progress
+    pub can_continue: bool,
+
+    difficulty_options: Vec<Button>,
+    is_choosing_difficulty: bool,
+
+    focus: Focus,
 }
 
 impl Menu {
@@ -16,74 +28,129 @@ impl Menu {
         return Self {
             title: "Becoming the Banana Man".to_string(),
             options: vec![
-                Button::new(
-                    vec2(screen_width() / 2. - 100., screen_height() / 2. - 200.),
-                    vec2(200., 50.),
-                    "Play".to_string(),
-                    RED,
-                    15,
-                )
-                .await,
-                Button::new(
-                    vec2(screen_width() - 220., 20.),
-                    vec2(200., 50.),
-                    "Tutorial".to_string(),
-                    RED,
-                    14,
-                )
-                .await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Continue".to_string(), RED, 15).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "New Game".to_string(), RED, 15).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Tutorial".to_string(), RED, 14).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Stats".to_string(), RED, 15).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Leaderboard".to_string(), RED, 15).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Achievements".to_string(), RED, 15).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Options".to_string(), RED, 15).await,
             ],
             game_state: GameState::Menu,
 
-            can_press_options: false,
+            start_new_game: false,
+            difficulty: Difficulty::default(),
+            can_continue: false,
+
+            difficulty_options: vec![
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Casual".to_string(), RED, 15).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Normal".to_string(), RED, 15).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Hardcore".to_string(), RED, 15).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Back".to_string(), RED, 15).await,
+            ],
+            is_choosing_difficulty: false,
+
+            focus: Focus::default(),
         };
     }
 
-    pub async fn start(&mut self) {
-        if !is_mouse_button_pressed(MouseButton::Left) {
-            self.can_press_options = true;
+    async fn choose_difficulty(&mut self, font: Font) {
+        let column = Stack::column(Anchor::Center, BUTTON_SIZE, self.difficulty_options.len());
+
+        self.focus.update_buttons(&mut self.difficulty_options);
+
+        for (i, option) in self.difficulty_options.iter_mut().enumerate() {
+            let rect = column.rect(i);
+
+            option.set_rect(rect);
+            option.draw().await;
+
+            let difficulty = Difficulty::ALL
+                .into_iter()
+                .find(|difficulty| difficulty.name() == option.text);
+
+            // Describe the difficulty next to its button
+            if let Some(difficulty) = difficulty {
+                layout::draw_text_in(
+                    difficulty.description(),
+                    Rect::new(
+                        rect.right() + layout::SPACING * layout::scale(),
+                        rect.y,
+                        screen_width() - rect.right(),
+                        rect.h,
+                    ),
+                    Anchor::Left,
+                    font,
+                    layout::font_size(18),
+                    BLACK,
+                );
+            }
+
+            if !option.is_clicked() {
+                continue;
+            }
+
+            // Back just closes the difficulty screen
+            self.is_choosing_difficulty = false;
+            self.focus = Focus::default();
+
+            if let Some(difficulty) = difficulty {
+                self.difficulty = difficulty;
+                self.start_new_game = true;
+                self.game_state = GameState::Battling;
+            }
         }
+    }
+
+    pub async fn start(&mut self) {
+        let font = load_ttf_font("res/Roboto-Medium.ttf").await.unwrap();
 
         // Draw title
-        draw_text_ex(
+        layout::draw_text_anchored(
             self.title.as_str(),
-            screen_width() * 0.5 - 7. - self.title.to_string().chars().count() as f32 * 7.,
-            30.,
-            TextParams {
-                font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
-                font_size: 30,
-                color: BLACK,
-                font_scale: 1.,
-                font_scale_aspect: 1.,
-            },
+            Anchor::Top,
+            Vec2::ZERO,
+            font,
+            30,
+            BLACK,
         );
 
+        if self.is_choosing_difficulty {
+            self.choose_difficulty(font).await;
+
+            return;
+        }
+
+        let column = Stack::column(Anchor::Center, BUTTON_SIZE, self.options.len())
+            .with_offset(vec2(0., 20.));
+
+        self.focus.update_buttons(&mut self.options);
+
         // Draw buttons
-        for option in &mut self.options {
+        for (i, option) in self.options.iter_mut().enumerate() {
+            // There is nothing to continue before the first game
+            if option.text == "Continue" {
+                option.is_disabled = !self.can_continue;
+            }
+
+            option.set_rect(column.rect(i));
             option.draw().await;
 
-            // Update button position
+            if !option.is_clicked() {
+                continue;
+            }
+
             match option.text.as_str() {
-                "Play" => {
-                    option.update(
-                        vec2(screen_width() / 2. - 100., screen_height() / 2. - 55.),
-                        vec2(200., 50.),
-                    );
-
-                    if option.is_pressed() && self.can_press_options {
-                        self.game_state = GameState::Battling;
-                    }
-                }
-                "Tutorial" => {
-                    option.update(
-                        vec2(screen_width() / 2. - 100., screen_height() / 2. + 5.),
-                        vec2(200., 50.),
-                    );
-
-                    if option.is_pressed() && self.can_press_options {
-                        self.game_state = GameState::Tutorial;
-                    }
+                "Continue" => self.game_state = GameState::Battling,
+                "New Game" => {
+                    self.is_choosing_difficulty = true;
+                    self.focus = Focus::default();
                 }
+                "Tutorial" => self.game_state = GameState::Tutorial,
+                "Stats" => self.game_state = GameState::Statistics,
+                "Leaderboard" => self.game_state = GameState::HighScores,
+                "Achievements" => self.game_state = GameState::Achievements,
+                "Options" => self.game_state = GameState::Options,
                 _ => {}
             }
         }
@@ -1,6 +1,11 @@
+pub mod achievement_gallery;
 pub mod battling;
 pub mod dead;
 pub mod game_state;
+pub mod high_scores;
 pub mod menu;
+pub mod options;
+pub mod statistics;
 pub mod tutorial;
 pub mod upgrading;
+pub mod victory;
@@ -0,0 +1,117 @@
+use crate::objects::button::Button;
+use crate::objects::checkbox::Checkbox;
+use crate::objects::focus::Focus;
+use crate::objects::layout::{self, Anchor, Stack, BUTTON_SIZE};
+use crate::objects::settings::Settings;
+use crate::objects::slider::Slider;
+use crate::states::game_state::GameState;
+
+use macroquad::prelude::*;
+
+const WIDGET_SIZE: Vec2 = const_vec2!([400., 50.]);
+
+pub struct Options {
+    pub game_state: GameState,
+    pub settings: Settings,
+
+    title: String,
+    music_checkbox: Checkbox,
+    music_volume_slider: Slider,
+    particle_density_slider: Slider,
+    screen_shake_slider: Slider,
+    hit_stop_checkbox: Checkbox,
+    zoom_punch_checkbox: Checkbox,
+    back_button: Button,
+    font: Font,
+    focus: Focus,
+}
+
+impl Options {
+    pub async fn new() -> Self {
+        Self {
+            game_state: GameState::Options,
+            settings: Settings::default(),
+
+            title: "Options".to_string(),
+            music_checkbox: Checkbox::new("Music".to_string(), true).await,
+            music_volume_slider: Slider::new("Music volume".to_string(), 0., 100., 5., 50.).await,
+            particle_density_slider: Slider::new("Particles".to_string(), 0., 100., 25., 100.)
+                .await,
+            screen_shake_slider: Slider::new("Screen shake".to_string(), 0., 100., 25., 100.).await,
+            hit_stop_checkbox: Checkbox::new("Hit-stop on kills".to_string(), true).await,
+            zoom_punch_checkbox: Checkbox::new("Zoom on upgrades".to_string(), true).await,
+            back_button: Button::new(Vec2::ZERO, BUTTON_SIZE, "Back".to_string(), RED, 15).await,
+            font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
+            focus: Focus::default(),
+        }
+    }
+
+    pub async fn start(&mut self) {
+        let font = self.font;
+
+        // Draw title
+        layout::draw_text_anchored(
+            self.title.as_str(),
+            Anchor::Top,
+            Vec2::ZERO,
+            font,
+            30,
+            BLACK,
+        );
+
+        // Show the current settings
+        self.music_checkbox.is_checked = self.settings.is_music_on;
+        self.music_volume_slider.value = (self.settings.music_volume * 100.).round();
+        self.particle_density_slider.value = (self.settings.particle_density * 100.).round();
+        self.screen_shake_slider.value = (self.settings.screen_shake * 100.).round();
+        self.hit_stop_checkbox.is_checked = self.settings.is_hit_stop_on;
+        self.zoom_punch_checkbox.is_checked = self.settings.is_zoom_punch_on;
+
+        self.focus.update(7);
+        self.music_checkbox.is_focused = self.focus.is_focused(0);
+        self.music_volume_slider.is_focused = self.focus.is_focused(1);
+        self.particle_density_slider.is_focused = self.focus.is_focused(2);
+        self.screen_shake_slider.is_focused = self.focus.is_focused(3);
+        self.hit_stop_checkbox.is_focused = self.focus.is_focused(4);
+        self.zoom_punch_checkbox.is_focused = self.focus.is_focused(5);
+        self.back_button.is_focused = self.focus.is_focused(6);
+
+        let column = Stack::column(Anchor::Center, WIDGET_SIZE, 6);
+
+        self.music_checkbox.update(column.rect(0));
+        self.music_checkbox.draw();
+
+        self.music_volume_slider.update(column.rect(1));
+        self.music_volume_slider.draw();
+
+        self.particle_density_slider.update(column.rect(2));
+        self.particle_density_slider.draw();
+
+        self.screen_shake_slider.update(column.rect(3));
+        self.screen_shake_slider.draw();
+
+        self.hit_stop_checkbox.update(column.rect(4));
+        self.hit_stop_checkbox.draw();
+
+        self.zoom_punch_checkbox.update(column.rect(5));
+        self.zoom_punch_checkbox.draw();
+
+        self.back_button
+            .set_rect(layout::place(Anchor::TopLeft, BUTTON_SIZE, Vec2::ZERO));
+        self.back_button.draw().await;
+
+        // Take the changed settings
+        self.settings.is_music_on = self.music_checkbox.is_checked;
+        self.settings.music_volume = self.music_volume_slider.value / 100.;
+        self.settings.particle_density = self.particle_density_slider.value / 100.;
+        self.settings.screen_shake = self.screen_shake_slider.value / 100.;
+        self.settings.is_hit_stop_on = self.hit_stop_checkbox.is_checked;
+        self.settings.is_zoom_punch_on = self.zoom_punch_checkbox.is_checked;
+
+        if self.back_button.is_clicked() || is_key_pressed(KeyCode::Escape) {
+            self.settings.save();
+
+            self.game_state = GameState::Menu;
+        }
+    }
+}
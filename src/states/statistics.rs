@@ -0,0 +1,103 @@
+use crate::objects::button::Button;
+use crate::objects::layout::{self, Anchor, Stack, BUTTON_SIZE};
+use crate::objects::stats::Stats;
+use crate::states::game_state::GameState;
+use crate::upgrading::tier::TIERS;
+
+use macroquad::prelude::*;
+
+// Size of one line of the stats in unscaled units
+const LINE_SIZE: Vec2 = const_vec2!([300., 25.]);
+
+pub struct Statistics {
+    pub game_state: GameState,
+    pub stats: Stats,
+
+    title: String,
+    back_button: Button,
+    font: Font,
+}
+
+fn format_time(seconds: f32) -> String {
+    format!(
+        "{}:{:02}:{:02}",
+        seconds as usize / 3600,
+        seconds as usize / 60 % 60,
+        seconds as usize % 60
+    )
+}
+
+impl Statistics {
+    pub async fn new() -> Self {
+        Self {
+            game_state: GameState::Statistics,
+            stats: Stats::default(),
+
+            title: "Statistics".to_string(),
+            back_button: Button::new(Vec2::ZERO, BUTTON_SIZE, "Back".to_string(), RED, 15).await,
+            font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
+        }
+    }
+
+    pub async fn start(&mut self) {
+        let font = self.font;
+
+        // Draw title
+        layout::draw_text_anchored(
+            self.title.as_str(),
+            Anchor::Top,
+            Vec2::ZERO,
+            font,
+            30,
+            BLACK,
+        );
+
+        let mut lines = vec![
+            format!("Time played: {}", format_time(self.stats.time_played)),
+            format!("Shots fired: {}", self.stats.shots_fired),
+            format!("Accuracy: {:.1}%", self.stats.accuracy()),
+            format!("Kills: {}", self.stats.kills),
+            format!("Bosses defeated: {}", self.stats.bosses_defeated),
+            format!("Money earned: ${}", self.stats.money_earned),
+            format!("Money spent: ${}", self.stats.money_spent),
+            format!("Bullets bought: {}", self.stats.bullets_bought),
+            format!("Deaths: {}", self.stats.deaths),
+            format!("Retreats: {}", self.stats.retreats),
+        ];
+
+        for (i, tier) in TIERS.iter().enumerate() {
+            lines.push(format!(
+                "Time as {}: {}",
+                tier.name,
+                format_time(self.stats.time_per_tier[i])
+            ));
+        }
+
+        // Draw the stats in two columns
+        let half = lines.len().div_ceil(2);
+
+        for (i, line) in lines.iter().enumerate() {
+            let column = Stack::column(Anchor::Top, LINE_SIZE, half).with_offset(vec2(
+                if i < half { -0.5 } else { 0.5 } * (LINE_SIZE.x + layout::SPACING),
+                60.,
+            ));
+
+            layout::draw_text_in(
+                line.as_str(),
+                column.rect(i % half),
+                Anchor::Left,
+                font,
+                layout::font_size(22),
+                BLACK,
+            );
+        }
+
+        self.back_button
+            .set_rect(layout::place(Anchor::TopLeft, BUTTON_SIZE, Vec2::ZERO));
+        self.back_button.draw().await;
+
+        if self.back_button.is_clicked() {
+            self.game_state = GameState::Menu;
+        }
+    }
+}
@@ -1,20 +1,16 @@
 use crate::objects::button::Button;
+use crate::objects::layout::{self, Anchor, BUTTON_SIZE};
 use crate::states::game_state::GameState;
 
-use imagesize::size;
-
 use macroquad::prelude::*;
 
 pub struct Tutorial {
     pub game_state: GameState,
 
-    messages: [[String; 2]; 8],
+    messages: [[String; 2]; 11],
     message_index: usize,
 
     options: Vec<Button>,
-
-    can_press_next_button: bool,
-    can_press_previous_button: bool,
 }
 
 impl Tutorial {
@@ -32,11 +28,19 @@ impl Tutorial {
                     "res/tutorial/shoot.png".to_string(),
                 ],
                 [
-                    "When you kill an enemy, you get money".to_string(),
+                    "Press R to reload, or wait for an empty magazine to reload".to_string(),
+                    "".to_string(),
+                ],
+                [
+                    "Killed enemies drop money and ammo, walk over it to collect".to_string(),
                     "res/tutorial/kill.png".to_string(),
                 ],
                 [
-                    "If you get hit or run out of bullets, you die".to_string(),
+                    "Walk over power-ups to get a boost for a few seconds".to_string(),
+                    "".to_string(),
+                ],
+                [
+                    "If you lose all your HP or run out of bullets, you die".to_string(),
                     "res/tutorial/die.png".to_string(),
                 ],
                 [
@@ -44,7 +48,7 @@ impl Tutorial {
                     "res/tutorial/retreat.png".to_string(),
                 ],
                 [
-                    "Click the upgrade button to upgrade your character".to_string(),
+                    "Beat the boss, then click the upgrade button to upgrade".to_string(),
                     "res/tutorial/upgrade.png".to_string(),
                 ],
                 [
@@ -55,75 +59,52 @@ impl Tutorial {
                     "Click the battle button to go back to battling".to_string(),
                     "res/tutorial/battle.png".to_string(),
                 ],
+                [
+                    "Press space to use your character's ability".to_string(),
+                    "".to_string(),
+                ],
             ],
             message_index: 0,
 
             options: vec![
-                Button::new(
-                    vec2(screen_width() - 220., screen_height() - 100.),
-                    vec2(200., 50.),
-                    "Next".to_string(),
-                    RED,
-                    15,
-                )
-                .await,
-                Button::new(
-                    vec2(20., screen_height() - 100.),
-                    vec2(200., 50.),
-                    "Previous".to_string(),
-                    RED,
-                    15,
-                )
-                .await,
-                Button::new(
-                    vec2(20., 20.),
-                    vec2(200., 50.),
-                    "Close".to_string(),
-                    RED,
-                    15,
-                )
-                .await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Next".to_string(), RED, 15).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Previous".to_string(), RED, 15).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Close".to_string(), RED, 15).await,
             ],
-
-            can_press_next_button: false,
-            can_press_previous_button: false,
         };
     }
 
     pub async fn start(&mut self) {
         // Draw message
-        draw_text_ex(
+        layout::draw_text_anchored(
             self.messages[self.message_index as usize][0].as_str(),
-            screen_width() * 0.5
-                - 7.
-                - self.messages[self.message_index as usize][0]
-                    .to_string()
-                    .chars()
-                    .count() as f32
-                    * 7.,
-            100.,
-            TextParams {
-                font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
-                font_size: 30,
-                color: BLACK,
-                font_scale: 1.,
-                font_scale_aspect: 1.,
-            },
+            Anchor::Top,
+            vec2(0., 60.),
+            load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
+            30,
+            BLACK,
         );
 
         if self.messages[self.message_index as usize][1] != "" {
+            let texture = load_texture(self.messages[self.message_index as usize][1].as_str())
+                .await
+                .unwrap();
+            let rect = layout::place(
+                Anchor::Center,
+                vec2(texture.width(), texture.height()),
+                Vec2::ZERO,
+            );
+
             // Draw texture
-            draw_texture(
-                load_texture(self.messages[self.message_index as usize][1].as_str())
-                    .await
-                    .unwrap(),
-                screen_width() * 0.5 - 200.,
-                screen_height() / 2.
-                    - (size(self.messages[self.message_index as usize][1].clone())
-                        .unwrap()
-                        .height
-                        / 2) as f32,
+            draw_texture_ex(
+                texture,
+                rect.x,
+                rect.y,
                 WHITE,
+                DrawTextureParams {
+                    dest_size: Some(rect.size()),
+                    ..Default::default()
+                },
             );
         }
 
@@ -135,17 +116,14 @@ impl Tutorial {
                     if self.message_index < self.messages.len() - 1 {
                         option.draw().await;
 
-                        option.update(
-                            vec2(screen_width() - 220., screen_height() - 100.),
-                            vec2(200., 50.),
-                        );
+                        option.set_rect(layout::place(
+                            Anchor::BottomRight,
+                            BUTTON_SIZE,
+                            vec2(0., -30.),
+                        ));
 
-                        if option.is_pressed() && self.can_press_next_button {
+                        if option.is_clicked() {
                             self.message_index += 1;
-
-                            self.can_press_next_button = false;
-                        } else if !option.is_pressed() {
-                            self.can_press_next_button = true;
                         }
                     }
                 }
@@ -153,23 +131,23 @@ impl Tutorial {
                     if self.message_index > 0 {
                         option.draw().await;
 
-                        option.update(vec2(20., screen_height() - 100.), vec2(200., 50.));
+                        option.set_rect(layout::place(
+                            Anchor::BottomLeft,
+                            BUTTON_SIZE,
+                            vec2(0., -30.),
+                        ));
 
-                        if option.is_pressed() && self.can_press_previous_button {
+                        if option.is_clicked() {
                             self.message_index -= 1;
-
-                            self.can_press_previous_button = false;
-                        } else if !option.is_pressed() {
-                            self.can_press_previous_button = true;
                         }
                     }
                 }
                 "Close" => {
                     option.draw().await;
 
-                    option.update(vec2(20., 20.), vec2(200., 50.));
+                    option.set_rect(layout::place(Anchor::TopLeft, BUTTON_SIZE, Vec2::ZERO));
 
-                    if option.is_pressed() {
+                    if option.is_clicked() {
                         self.game_state = GameState::Menu;
                     }
                 }
@@ -1,29 +1,55 @@
+use crate::battling::boss::BOSSES;
 use crate::objects::bullets::Bullets;
 use crate::objects::button::Button;
+use crate::objects::dialog::Dialog;
+use crate::objects::focus::Focus;
+use crate::objects::layout::{Anchor, Stack, BUTTON_SIZE};
 use crate::objects::money::Money;
+use crate::objects::stats::StatEvent;
+use crate::objects::text::Text;
 use crate::states::game_state::GameState;
-use crate::upgrading::bullet_button::BulletButton;
+use crate::upgrading::bullet_shop::BulletShop;
 use crate::upgrading::character::Character;
+use crate::upgrading::character_gallery::CharacterGallery;
+use crate::upgrading::stat_tree::StatTree;
+use crate::upgrading::tier::{self, Tier};
+use crate::upgrading::upgrades::Upgrades;
 
 use macroquad::prelude::*;
 
+// Characters at least this expensive ask before being bought
+const CONFIRM_PRICE: usize = 1000;
+// Seconds the not enough money warning is shown for
+const WARNING_TIME: f32 = 1.5;
+
 pub struct Upgrading {
     pub game_state: GameState,
     pub money: Money,
     pub bullets: Bullets,
     pub character: Character,
-
-    // (Character Name, Price)
-    characters: [(String, usize); 4],
+    pub upgrades: Upgrades,
+    pub bosses_defeated: usize,
+    // Set when the upgrade button starts a boss fight
+    pub start_boss_fight: bool,
+    // Things that happened this frame, for the stats to record
+    pub stat_events: Vec<StatEvent>,
 
     upgrade_button: Button,
     buy_bullets_button: Button,
+    stats_button: Button,
     battle_button: Button,
 
-    buy_bullet_buttons: [BulletButton; 4],
+    bullet_shop: BulletShop,
+    character_gallery: CharacterGallery,
+    stat_tree: StatTree,
+
+    is_showing_bullet_shop: bool,
+    is_showing_stat_tree: bool,
+    focus: Focus,
 
-    can_press_buy_bullet_buttons: bool,
-    is_showing_buy_bullet_buttons: bool,
+    confirm_dialog: Dialog,
+    warning_text: Text,
+    warning_timer: f32,
 }
 
 impl Upgrading {
@@ -33,70 +59,85 @@ impl Upgrading {
             money: Money::new().await,
             bullets: Bullets::new().await,
             character: Character::new().await,
+            upgrades: Upgrades::default(),
+            bosses_defeated: 0,
+            start_boss_fight: false,
+            stat_events: Vec::new(),
 
-            characters: [
-                ("Orange".to_string(), 100),
-                ("Pear".to_string(), 500),
-                ("Pineapple".to_string(), 1000),
-                ("Banana".to_string(), 10000),
-            ],
-
-            buy_bullet_buttons: [
-                BulletButton::new(1., 100.).await,
-                BulletButton::new(2., 50.).await,
-                BulletButton::new(3., 10.).await,
-                BulletButton::new(4., 1.).await,
-            ],
-            upgrade_button: Button::new(
-                vec2(20., screen_height() - 180.),
-                vec2(200., 50.),
-                "Upgrade".to_string(),
-                GRAY,
-                15,
-            )
-            .await,
+            bullet_shop: BulletShop::new().await,
+            character_gallery: CharacterGallery::new().await,
+            stat_tree: StatTree::new().await,
+            stats_button: Button::new(Vec2::ZERO, BUTTON_SIZE, "Stats".to_string(), GRAY, 15).await,
+            upgrade_button: Button::new(Vec2::ZERO, BUTTON_SIZE, "Upgrade".to_string(), GRAY, 15)
+                .await,
             buy_bullets_button: Button::new(
-                vec2(20., screen_height() - 120.),
-                vec2(200., 50.),
+                Vec2::ZERO,
+                BUTTON_SIZE,
                 "Buy Bullets".to_string(),
                 GRAY,
                 15,
             )
             .await,
-            battle_button: Button::new(
-                vec2(20., screen_height() - 60.),
-                vec2(200., 50.),
-                "Battle".to_string(),
-                GRAY,
-                15,
+            battle_button: Button::new(Vec2::ZERO, BUTTON_SIZE, "Battle".to_string(), GRAY, 15)
+                .await,
+
+            is_showing_bullet_shop: false,
+            is_showing_stat_tree: false,
+            focus: Focus::default(),
+
+            confirm_dialog: Dialog::new().await,
+            warning_text: Text::new(
+                Anchor::Top,
+                vec2(0., 40.),
+                "res/Roboto-Medium.ttf".to_string(),
+                "Not enough money!".to_string(),
+                20,
+                RED,
             )
             .await,
-
-            can_press_buy_bullet_buttons: true,
-            is_showing_buy_bullet_buttons: false,
+            warning_timer: 0.,
         };
     }
 
     pub async fn start(&mut self) {
-        self.battle_button.draw().await;
-        self.battle_button
-            .update(vec2(20., screen_height() - 60.), vec2(200., 50.));
+        // Nothing else can be pressed while a purchase is being confirmed
+        if self.confirm_dialog.is_open {
+            self.confirm_stuff().await;
+
+            return;
+        }
 
+        // The buttons are stacked in the bottom left corner
+        let column = Stack::column(Anchor::BottomLeft, BUTTON_SIZE, 4);
+
+        // Change the upgrade button text and whether it can be pressed
+        self.set_upgrade_button_state();
+
+        self.focus.update(4);
+        self.stats_button.is_focused = self.focus.is_focused(0);
+        self.upgrade_button.is_focused = self.focus.is_focused(1);
+        self.buy_bullets_button.is_focused = self.focus.is_focused(2);
+        self.battle_button.is_focused = self.focus.is_focused(3);
+
+        self.stats_button.set_rect(column.rect(0));
+        self.stats_button.draw().await;
+
+        self.upgrade_button.set_rect(column.rect(1));
         self.upgrade_button.draw().await;
-        self.upgrade_button.update(
-            vec2(self.buy_bullets_button.rect.x, screen_height() - 180.),
-            self.upgrade_button.rect.size(),
-        );
 
+        self.buy_bullets_button.set_rect(column.rect(2));
         self.buy_bullets_button.draw().await;
-        self.buy_bullets_button.update(
-            vec2(self.buy_bullets_button.rect.x, screen_height() - 120.),
-            self.buy_bullets_button.rect.size(),
-        );
+
+        self.battle_button.set_rect(column.rect(3));
+        self.battle_button.draw().await;
 
         self.money.draw();
         self.bullets.draw();
-        self.character.draw();
+
+        // The stat tree covers the characters
+        if !self.is_showing_stat_tree {
+            self.character_gallery_stuff().await;
+        }
 
         // Check if you need to upgrade the character
         self.check_upgrade().await;
@@ -104,122 +145,215 @@ impl Upgrading {
         // Check if you need to battle
         self.check_battle_button();
 
-        // Change the upgrade button text
-        self.set_upgrade_button_text();
+        // Everything to do with the bullet shop
+        self.bullet_shop_stuff().await;
+
+        // Everything to do with the stat tree
+        self.stat_tree_stuff().await;
+
+        self.draw_warning();
+
+        // Tooltips go on top of everything
+        self.upgrade_button.draw_tooltip();
+    }
+
+    async fn confirm_stuff(&mut self) {
+        // Keep the screen behind the dialog
+        self.stats_button.draw().await;
+        self.upgrade_button.draw().await;
+        self.buy_bullets_button.draw().await;
+        self.battle_button.draw().await;
+
+        self.money.draw();
+        self.bullets.draw();
+        self.character_gallery.draw().await;
+
+        let answer = self.confirm_dialog.update();
+        self.confirm_dialog.draw().await;
+
+        if answer == Some(true) {
+            if let Some(next) = tier::next(&self.character.character) {
+                self.buy_character(next).await;
+            }
+        }
+    }
+
+    async fn character_gallery_stuff(&mut self) {
+        let current = tier::index(&self.character.character);
+
+        // Show the new character after upgrading or starting again
+        if self.character_gallery.current != current {
+            self.character_gallery.current = current;
+            self.character_gallery.show_current();
+        }
+
+        self.character_gallery.is_showing_details = !self.is_showing_bullet_shop;
+        // The focused button and the shop's slider need the arrow keys more
+        self.character_gallery.is_using_arrow_keys =
+            self.focus.index.is_none() && !self.is_showing_bullet_shop;
 
-        // Everything to do with the bullet buttons
-        self.buy_bullet_buttons_stuff().await;
+        self.character_gallery.update();
+        self.character_gallery.draw().await;
     }
 
-    fn set_upgrade_button_text(&mut self) {
+    fn draw_warning(&mut self) {
+        self.warning_timer = (self.warning_timer - get_frame_time()).max(0.);
+
+        if self.warning_timer > 0. {
+            // Fade out during the last half second
+            self.warning_text.color.a = (self.warning_timer * 2.).min(1.);
+            self.warning_text.draw();
+        }
+    }
+
+    fn set_upgrade_button_state(&mut self) {
+        let next = tier::next(&self.character.character);
+
+        // The upgrade can't be bought without the money, but the boss can always be fought
+        self.upgrade_button.is_disabled = match next {
+            Some(next) => self.is_boss_defeated() && self.money.money < next.price,
+            None => true,
+        };
+
+        self.upgrade_button.tooltip = match next {
+            Some(next) if !self.is_boss_defeated() => {
+                Some(format!("Beat the boss to unlock the {}", next.name))
+            }
+            Some(next) => Some(format!(
+                "Price: ${}, you have ${}",
+                next.price, self.money.money
+            )),
+            None => None,
+        };
+
         self.upgrade_button.text = format!(
             "Upgrade: {}",
-            if self.character.character == "Banana".to_string() {
-                "Max".to_string()
-            } else {
-                format!(
-                    "${}",
-                    // Cost to upgrade (The price of the next character)
-                    self.characters[self
-                        .characters
-                        .iter()
-                        .position(|r| *r.0 == self.character.character)
-                        .unwrap_or_default()]
-                    .1
-                    .to_string()
-                )
+            match tier::next(&self.character.character) {
+                // The boss has to be beaten first
+                Some(_) if !self.is_boss_defeated() => format!(
+                    "Beat {}",
+                    BOSSES[tier::index(&self.character.character)].name
+                ),
+                // Cost to upgrade (The price of the next character)
+                Some(next) => format!("${}", next.price),
+                None => "Max".to_string(),
             }
-        )
-        .to_string();
+        );
     }
 
     fn check_battle_button(&mut self) {
-        if self.battle_button.is_pressed() {
+        if self.battle_button.is_clicked() {
             self.game_state = GameState::Battling;
         }
     }
 
-    async fn buy_bullet_buttons_stuff(&mut self) {
-        if !self.buy_bullets_button.is_pressed() {
-            self.can_press_buy_bullet_buttons = true
+    async fn bullet_shop_stuff(&mut self) {
+        if self.buy_bullets_button.is_clicked() {
+            self.is_showing_bullet_shop = !self.is_showing_bullet_shop;
+        }
+
+        if self.is_showing_bullet_shop {
+            // Give the shop some data
+            self.bullet_shop.bullets = self.bullets.bullets;
+            self.bullet_shop.money = self.money.money;
+
+            self.bullet_shop.update();
+            self.bullet_shop.draw().await;
+
+            if self.bullet_shop.is_rejected() {
+                self.warning_timer = WARNING_TIME;
+            }
+
+            // Record what was bought and sold
+            self.stat_events.append(&mut self.bullet_shop.stat_events);
+
+            // Take the mutated data from the shop
+            self.bullets.bullets = self.bullet_shop.bullets;
+            self.money.money = self.bullet_shop.money;
+
+            self.bullet_shop.draw_tooltips();
         }
+    }
 
-        if self.buy_bullets_button.is_pressed()
-            && !self.is_showing_buy_bullet_buttons
-            && self.can_press_buy_bullet_buttons
-        {
-            self.is_showing_buy_bullet_buttons = true;
-            self.can_press_buy_bullet_buttons = false;
-        } else if self.buy_bullets_button.is_pressed()
-            && self.is_showing_buy_bullet_buttons
-            && self.can_press_buy_bullet_buttons
-        {
-            self.is_showing_buy_bullet_buttons = false;
-            self.can_press_buy_bullet_buttons = false;
+    async fn stat_tree_stuff(&mut self) {
+        if self.stats_button.is_clicked() {
+            self.is_showing_stat_tree = !self.is_showing_stat_tree;
         }
 
-        if self.is_showing_buy_bullet_buttons {
-            for button in self.buy_bullet_buttons.iter_mut() {
-                // Give button some data
-                button.bullets = self.bullets.bullets;
-                button.money = self.money.money;
+        if self.is_showing_stat_tree {
+            // Give the tree some data
+            self.stat_tree.upgrades = self.upgrades.clone();
+            self.stat_tree.money = self.money.money;
 
-                button.draw().await;
-                button.update();
+            self.stat_tree.draw().await;
+            self.stat_tree.update();
 
-                // Take the mutated data from the button
-                self.bullets.bullets = button.bullets;
-                self.money.money = button.money;
+            // Record what was bought
+            if self.stat_tree.money < self.money.money {
+                self.stat_events.push(StatEvent::MoneySpent(
+                    self.money.money - self.stat_tree.money,
+                ));
             }
+
+            // Take the mutated data from the tree
+            self.upgrades = self.stat_tree.upgrades.clone();
+            self.money.money = self.stat_tree.money;
         }
     }
 
+    fn is_boss_defeated(&self) -> bool {
+        self.bosses_defeated > tier::index(&self.character.character)
+    }
+
     async fn check_upgrade(&mut self) {
-        if self.upgrade_button.is_pressed()
-            // Check if there are new characters to purchase
-            && self
-                .characters
-                .iter()
-                .position(|r| *r.0 == self.character.character)
-                .unwrap_or_default()
-                < self.characters.len() - 1
-            && self.character.can_set
-            // Check if you have enough money to purchase the new character
-            && self.money.money
-                >= self.characters[self
-                    .characters
-                    .iter()
-                    .position(|r| *r.0 == self.character.character)
-                    .unwrap_or_default()]
-                .1
-        {
-            // Take away money
-            self.money.money -= self.characters[self
-                .characters
-                .iter()
-                .position(|r| *r.0 == self.character.character)
-                .unwrap_or_default()]
-            .1;
-
-            // Change character
-            self.character
-                .set(if self.character.character == "Apple" {
-                    "Orange".to_string()
-                } else {
-                    (&self.characters[self
-                        .characters
-                        .iter()
-                        .position(|r| *r.0 == self.character.character)
-                        .unwrap_or_default()
-                        + 1]
-                    .0)
-                        .to_owned()
-                })
-                .await;
-
-            self.character.can_set = false;
-        } else if !self.upgrade_button.is_pressed() {
-            self.character.can_set = true;
+        // Check if there are new characters to purchase
+        let next = tier::next(&self.character.character);
+
+        // Tell the player why nothing happened
+        if self.upgrade_button.is_rejected() && next.is_some() {
+            self.warning_timer = WARNING_TIME;
+        }
+
+        if self.upgrade_button.is_clicked() {
+            if let Some(next) = next {
+                if !self.is_boss_defeated() {
+                    // Fight the boss guarding the next character
+                    self.start_boss_fight = true;
+                    self.game_state = GameState::Battling;
+                } else if self.money.money >= next.price {
+                    if next.price >= CONFIRM_PRICE {
+                        // Make sure expensive characters aren't bought by accident
+                        self.confirm_dialog
+                            .open(format!("Buy the {} for ${}?", next.name, next.price));
+                    } else {
+                        self.buy_character(next).await;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn buy_character(&mut self, next: &Tier) {
+        if self.money.money < next.price {
+            return;
+        }
+
+        // Take away money
+        self.money.money -= next.price;
+        self.stat_events.push(StatEvent::MoneySpent(next.price));
+
+        // Change character
+        self.character_gallery.play_transformation(
+            tier::index(&self.character.character),
+            tier::index(next.name),
+        );
+        self.character.set(next.name.to_string()).await;
+        self.stat_events
+            .push(StatEvent::TierReached(tier::index(next.name)));
+
+        // Becoming the last character wins the game
+        if tier::next(next.name).is_none() {
+            self.game_state = GameState::Victory;
         }
     }
 }
@@ -0,0 +1,167 @@
+use crate::objects::button::Button;
+use crate::objects::focus::Focus;
+use crate::objects::layout::{self, Anchor, Stack, BUTTON_SIZE};
+use crate::objects::stats::Stats;
+use crate::states::game_state::GameState;
+
+use macroquad::prelude::*;
+
+const CREDITS_SPEED: f32 = 40.;
+// Space from one line of the summary or credits to the next, in unscaled units
+const SUMMARY_LINE_HEIGHT: f32 = 35.;
+const CREDITS_LINE_HEIGHT: f32 = 30.;
+// Where the summary starts under the title
+const SUMMARY_OFFSET: f32 = 60.;
+
+pub struct Victory {
+    pub game_state: GameState,
+    pub stats: Stats,
+
+    // Set when the player chooses to start over with their upgrades
+    pub start_new_game_plus: bool,
+    pub is_endless: bool,
+
+    title: String,
+    credits: [String; 6],
+    credits_offset: f32,
+    options: Vec<Button>,
+    font: Font,
+
+    focus: Focus,
+}
+
+impl Victory {
+    pub async fn new() -> Self {
+        Self {
+            game_state: GameState::Victory,
+            stats: Stats::default(),
+
+            start_new_game_plus: false,
+            is_endless: false,
+
+            title: "You became the Banana Man!".to_string(),
+            credits: [
+                "Becoming the Banana Man".to_string(),
+                "".to_string(),
+                "Game by Dequog".to_string(),
+                "Made with macroquad".to_string(),
+                "Font: Roboto".to_string(),
+                "Thanks for playing!".to_string(),
+            ],
+            credits_offset: 0.,
+            options: vec![
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Endless".to_string(), GOLD, 15).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "New Game+".to_string(), GOLD, 15).await,
+                Button::new(Vec2::ZERO, BUTTON_SIZE, "Menu".to_string(), RED, 15).await,
+            ],
+            font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
+
+            focus: Focus::default(),
+        }
+    }
+
+    pub async fn start(&mut self) {
+        let font = self.font;
+
+        // Draw title
+        layout::draw_text_anchored(
+            self.title.as_str(),
+            Anchor::Top,
+            Vec2::ZERO,
+            font,
+            30,
+            BLACK,
+        );
+
+        // Draw the stats summary
+        let summary = [
+            format!(
+                "Time played: {}:{:02}",
+                self.stats.time_played as usize / 60,
+                self.stats.time_played as usize % 60
+            ),
+            format!("Kills: {}", self.stats.kills),
+            format!("Money earned: ${}", self.stats.money_earned),
+        ];
+
+        layout::draw_lines(
+            &summary,
+            Anchor::Top,
+            vec2(0., SUMMARY_OFFSET),
+            SUMMARY_LINE_HEIGHT,
+            font,
+            25,
+            BLACK,
+        );
+
+        // The buttons are stacked at the bottom of the screen
+        let column = Stack::column(Anchor::Bottom, BUTTON_SIZE, self.options.len());
+
+        // The credits scroll up between the summary and the buttons
+        let top = layout::place(
+            Anchor::Top,
+            Vec2::ZERO,
+            vec2(
+                0.,
+                SUMMARY_OFFSET + summary.len() as f32 * SUMMARY_LINE_HEIGHT,
+            ),
+        )
+        .y;
+
+        self.draw_credits(font, top, column.rect(0).y);
+
+        self.focus.update_buttons(&mut self.options);
+
+        // Draw buttons
+        for (i, option) in self.options.iter_mut().enumerate() {
+            option.set_rect(column.rect(i));
+            option.draw().await;
+
+            if !option.is_clicked() {
+                continue;
+            }
+
+            match option.text.as_str() {
+                "Endless" => {
+                    // Keep playing as the Banana Man
+                    self.is_endless = true;
+                    self.game_state = GameState::Upgrading;
+                }
+                "New Game+" => {
+                    self.start_new_game_plus = true;
+                    self.game_state = GameState::Upgrading;
+                }
+                "Menu" => self.game_state = GameState::Menu,
+                _ => {}
+            }
+        }
+    }
+
+    fn draw_credits(&mut self, font: Font, top: f32, bottom: f32) {
+        let line_height = CREDITS_LINE_HEIGHT * layout::scale();
+
+        self.credits_offset += CREDITS_SPEED * layout::scale() * get_frame_time();
+
+        // Start again once all the credits have scrolled past
+        if self.credits_offset > bottom - top + self.credits.len() as f32 * line_height {
+            self.credits_offset = 0.;
+        }
+
+        for (i, line) in self.credits.iter().enumerate() {
+            let y = bottom - self.credits_offset + i as f32 * line_height;
+
+            if y < top || y + line_height > bottom {
+                continue;
+            }
+
+            layout::draw_text_in(
+                line.as_str(),
+                Rect::new(0., y, screen_width(), line_height),
+                Anchor::Top,
+                font,
+                layout::font_size(20),
+                DARKGRAY,
+            );
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use crate::objects::button::Button;
+use crate::objects::data_file::{self, ParseError};
+use crate::objects::layout::{self, Anchor, Stack};
+use crate::objects::slider::Slider;
+use crate::objects::stats::StatEvent;
+use crate::objects::text_input::TextInput;
+
+use macroquad::file::load_string;
+use macroquad::prelude::*;
+
+// Price of one bullet before any discount
+const BULLET_PRICE: usize = 1;
+// Percentage of the full price given back for selling a bullet
+const SELL_BACK_PERCENT: usize = 50;
+// The most bullets that can be bought or sold at once
+const MAX_QUANTITY: usize = 9999;
+const SLIDER_MAX: f32 = 1000.;
+const ROW_SIZE: Vec2 = const_vec2!([300., 50.]);
+const BULK_DISCOUNTS_FILE: &str = "res/bulk_discounts.txt";
+
+#[derive(Clone, Copy)]
+pub struct BulkDiscount {
+    // Bullets that have to be bought at once to get the discount
+    pub quantity: usize,
+    pub percent_off: usize,
+}
+
+// Used when the discounts file is missing
+const DEFAULT_BULK_DISCOUNTS: [BulkDiscount; 3] = [
+    BulkDiscount {
+        quantity: 50,
+        percent_off: 10,
+    },
+    BulkDiscount {
+        quantity: 100,
+        percent_off: 20,
+    },
+    BulkDiscount {
+        quantity: 500,
+        percent_off: 30,
+    },
+];
+
+// A discount line is the quantity and the percent off split by a comma
+fn parse_discount(text: &str) -> Option<BulkDiscount> {
+    let (quantity, percent_off) = text.split_once(',')?;
+
+    Some(BulkDiscount {
+        quantity: quantity.trim().parse().ok()?,
+        // More than 100% off would pay the player to buy bullets
+        percent_off: percent_off.trim().parse::<usize>().ok()?.min(100),
+    })
+}
+
+pub fn parse_bulk_discounts(file: &str) -> (Vec<BulkDiscount>, Vec<ParseError>) {
+    let mut discounts = Vec::new();
+    let (entries, mut errors) = data_file::parse(file);
+
+    for entry in entries {
+        match (entry.key, parse_discount(entry.value)) {
+            ("discount", Some(discount)) => discounts.push(discount),
+            ("discount", None) => errors.push(entry.error("expected quantity,percent_off")),
+            _ => errors.push(entry.error("unknown key")),
+        }
+    }
+
+    // Biggest discount last
+    discounts.sort_by_key(|discount| discount.quantity);
+
+    (discounts, errors)
+}
+
+pub async fn load_bulk_discounts() -> Vec<BulkDiscount> {
+    let Ok(file) = load_string(BULK_DISCOUNTS_FILE).await else {
+        return DEFAULT_BULK_DISCOUNTS.to_vec();
+    };
+
+    let (discounts, errors) = parse_bulk_discounts(file.as_str());
+    data_file::report(BULK_DISCOUNTS_FILE, &errors);
+
+    discounts
+}
+
+fn percent_off(discounts: &[BulkDiscount], quantity: usize) -> usize {
+    discounts
+        .iter()
+        .rev()
+        .find(|discount| quantity >= discount.quantity)
+        .map_or(0, |discount| discount.percent_off)
+}
+
+// Price of buying some bullets at once, rounded up
+pub fn price(discounts: &[BulkDiscount], quantity: usize) -> usize {
+    (quantity * BULLET_PRICE * (100 - percent_off(discounts, quantity))).div_ceil(100)
+}
+
+// Money given back for selling some bullets, rounded down
+pub fn sell_price(quantity: usize) -> usize {
+    quantity * BULLET_PRICE * SELL_BACK_PERCENT / 100
+}
+
+// The most bullets that can be bought with some money
+fn max_affordable(discounts: &[BulkDiscount], money: usize) -> usize {
+    // A bigger discount can make more bullets cheaper than fewer, so try every discount
+    let no_discount = money / BULLET_PRICE;
+
+    discounts
+        .iter()
+        // Free bullets are capped by the most that can be bought at once
+        .map(|discount| {
+            (money * 100)
+                .checked_div(BULLET_PRICE * (100 - discount.percent_off))
+                .unwrap_or(MAX_QUANTITY)
+        })
+        .filter(|quantity| price(discounts, *quantity) <= money)
+        .fold(no_discount, usize::max)
+        .min(MAX_QUANTITY)
+}
+
+pub struct BulletShop {
+    pub bullets: usize,
+    pub money: usize,
+    // Things that happened this frame, for the stats to record
+    pub stat_events: Vec<StatEvent>,
+
+    quantity: usize,
+    quantity_input: TextInput,
+    quantity_slider: Slider,
+    max_button: Button,
+    buy_button: Button,
+    sell_button: Button,
+    font: Font,
+    discounts: Vec<BulkDiscount>,
+}
+
+impl BulletShop {
+    pub async fn new() -> Self {
+        let mut quantity_input = TextInput::new(Vec2::ZERO, ROW_SIZE, 4).await;
+        quantity_input.is_numeric = true;
+        quantity_input.text = "1".to_string();
+
+        Self {
+            bullets: 0,
+            money: 0,
+            stat_events: Vec::new(),
+
+            quantity: 1,
+            quantity_input,
+            quantity_slider: Slider::new("Quantity".to_string(), 1., SLIDER_MAX, 1., 1.).await,
+            max_button: Button::new(Vec2::ZERO, ROW_SIZE, "Max Affordable".to_string(), GRAY, 15)
+                .await,
+            buy_button: Button::new(Vec2::ZERO, ROW_SIZE, "Buy".to_string(), GRAY, 15).await,
+            sell_button: Button::new(Vec2::ZERO, ROW_SIZE, "Sell".to_string(), GRAY, 15).await,
+            font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
+            discounts: load_bulk_discounts().await,
+        }
+    }
+
+    fn set_quantity(&mut self, quantity: usize) {
+        self.quantity = quantity.min(MAX_QUANTITY);
+        self.quantity_input.text = self.quantity.to_string();
+    }
+
+    // True for the frame the buy button was clicked without enough money
+    pub fn is_rejected(&self) -> bool {
+        self.buy_button.is_rejected()
+    }
+
+    // Must be called once every frame
+    pub fn update(&mut self) {
+        let column = Stack::column(Anchor::BottomRight, ROW_SIZE, 6);
+
+        // Typing a number changes the quantity, an empty input is nothing
+        self.quantity_input.rect = column.rect(1);
+        self.quantity_input.update(column.rect(1).point());
+        self.quantity = self.quantity_input.text.parse().unwrap_or(0);
+
+        // Dragging the slider changes the quantity
+        self.quantity_slider.value = (self.quantity as f32).clamp(1., SLIDER_MAX);
+        self.quantity_slider.update(column.rect(2));
+
+        if self.quantity_slider.value as usize != self.quantity.clamp(1, SLIDER_MAX as usize) {
+            self.set_quantity(self.quantity_slider.value as usize);
+        }
+
+        self.max_button.set_rect(column.rect(3));
+
+        if self.max_button.is_clicked() {
+            self.set_quantity(max_affordable(&self.discounts, self.money));
+        }
+
+        let price = price(&self.discounts, self.quantity);
+        let sell_price = sell_price(self.quantity);
+
+        self.buy_button.text = format!("Buy {} for ${}", self.quantity, price);
+        self.buy_button.is_disabled = self.quantity == 0 || self.money < price;
+        self.buy_button.tooltip = Some(format!("Price: ${}, you have ${}", price, self.money));
+        self.buy_button.set_rect(column.rect(4));
+
+        self.sell_button.text = format!("Sell {} for ${}", self.quantity, sell_price);
+        // One bullet sells for less than a dollar, so it can't be sold on its own
+        self.sell_button.is_disabled = sell_price == 0 || self.bullets < self.quantity;
+        self.sell_button.tooltip = Some(format!(
+            "Bullets sell for {}% of their price, you have {}",
+            SELL_BACK_PERCENT, self.bullets
+        ));
+        self.sell_button.set_rect(column.rect(5));
+
+        if self.buy_button.is_clicked() {
+            self.bullets += self.quantity;
+            self.money -= price;
+
+            self.stat_events
+                .push(StatEvent::BulletsBought(self.quantity));
+            self.stat_events.push(StatEvent::MoneySpent(price));
+        }
+
+        if self.sell_button.is_clicked() {
+            self.bullets -= self.quantity;
+            self.money += sell_price;
+
+            self.stat_events.push(StatEvent::MoneyEarned(sell_price));
+        }
+    }
+
+    pub async fn draw(&mut self) {
+        let column = Stack::column(Anchor::BottomRight, ROW_SIZE, 6);
+
+        // Show the discount the quantity gets and the next one to aim for
+        let next_discount = self
+            .discounts
+            .iter()
+            .find(|discount| self.quantity < discount.quantity);
+
+        let discount_text = match next_discount {
+            Some(next) => format!(
+                "{}% off, {}+ for {}% off",
+                percent_off(&self.discounts, self.quantity),
+                next.quantity,
+                next.percent_off
+            ),
+            None => format!("{}% off", percent_off(&self.discounts, self.quantity)),
+        };
+
+        layout::draw_text_centered(
+            discount_text.as_str(),
+            column.rect(0),
+            self.font,
+            layout::font_size(16),
+            BLACK,
+        );
+
+        self.quantity_input.draw();
+        self.quantity_slider.draw();
+        self.max_button.draw().await;
+        self.buy_button.draw().await;
+        self.sell_button.draw().await;
+    }
+
+    // Drawn after everything else so they go on top
+    pub fn draw_tooltips(&self) {
+        self.buy_button.draw_tooltip();
+        self.sell_button.draw_tooltip();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discount_starts_at_its_quantity() {
+        assert_eq!(price(&DEFAULT_BULK_DISCOUNTS, 49), 49);
+        assert_eq!(price(&DEFAULT_BULK_DISCOUNTS, 50), 45);
+        assert_eq!(price(&DEFAULT_BULK_DISCOUNTS, 99), 90);
+        assert_eq!(price(&DEFAULT_BULK_DISCOUNTS, 100), 80);
+        assert_eq!(price(&DEFAULT_BULK_DISCOUNTS, 499), 400);
+        assert_eq!(price(&DEFAULT_BULK_DISCOUNTS, 500), 350);
+    }
+
+    #[test]
+    fn sell_price_rounds_down() {
+        assert_eq!(sell_price(0), 0);
+        assert_eq!(sell_price(1), 0);
+        assert_eq!(sell_price(2), 1);
+        assert_eq!(sell_price(3), 1);
+    }
+
+    #[test]
+    fn max_affordable_reaches_discounts() {
+        assert_eq!(max_affordable(&DEFAULT_BULK_DISCOUNTS, 0), 0);
+        assert_eq!(max_affordable(&DEFAULT_BULK_DISCOUNTS, 44), 44);
+        // 50 bullets with 10% off cost 45
+        assert_eq!(max_affordable(&DEFAULT_BULK_DISCOUNTS, 45), 50);
+        assert_eq!(max_affordable(&DEFAULT_BULK_DISCOUNTS, 80), 100);
+        assert_eq!(max_affordable(&DEFAULT_BULK_DISCOUNTS, 350), 500);
+    }
+
+    #[test]
+    fn max_affordable_is_capped() {
+        assert_eq!(
+            max_affordable(&DEFAULT_BULK_DISCOUNTS, 1_000_000),
+            MAX_QUANTITY
+        );
+
+        let free = [BulkDiscount {
+            quantity: 1,
+            percent_off: 100,
+        }];
+        assert_eq!(max_affordable(&free, 0), MAX_QUANTITY);
+    }
+}
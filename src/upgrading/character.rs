@@ -1,3 +1,5 @@
+use crate::upgrading::tier::{self, Tier};
+
 use imagesize::size;
 
 use macroquad::prelude::*;
@@ -5,7 +7,6 @@ use macroquad::prelude::*;
 #[derive(Clone)]
 pub struct Character {
     pub character: String,
-    pub can_set: bool,
     pub texture: Texture2D,
     pub texture_size: (f32, f32),
     pub texture_file: String,
@@ -15,7 +16,6 @@ impl Character {
     pub async fn new() -> Self {
         Self {
             texture: load_texture("res/display/apple.png").await.unwrap(),
-            can_set: true,
             character: "Apple".to_string(),
             texture_size: (
                 size("res/regular/apple.png").unwrap().width as f32,
@@ -37,13 +37,7 @@ impl Character {
         self.texture_file = "res/display/".to_string() + texture.to_lowercase().as_str() + ".png"
     }
 
-    pub fn draw(&mut self) {
-        // Draw the Character
-        draw_texture(
-            self.texture,
-            screen_width() / 2. - size(self.texture_file.clone()).unwrap().width as f32 / 2.,
-            screen_height() / 2. - size(self.texture_file.clone()).unwrap().height as f32 / 2.,
-            WHITE,
-        )
+    pub fn tier(&self) -> &'static Tier {
+        tier::get(&self.character)
     }
 }
@@ -0,0 +1,210 @@
+use crate::objects::animation::{Animation, AnimationData};
+use crate::objects::button::Button;
+use crate::objects::layout::{self, Anchor};
+use crate::upgrading::tier::{Tier, TIERS};
+
+use macroquad::prelude::*;
+
+const ARROW_SIZE: Vec2 = const_vec2!([50., 50.]);
+// Where the character is drawn, from the centre of the screen
+const CHARACTER_OFFSET: Vec2 = const_vec2!([0., -60.]);
+const ENEMY_ICON_SIZE: f32 = 30.;
+
+// A carousel of every character, so the player can see what they are working towards
+pub struct CharacterGallery {
+    // The tier the player has
+    pub current: usize,
+    // The shop covers the details, so they can be hidden
+    pub is_showing_details: bool,
+    // The arrow keys are left alone while a widget is using them
+    pub is_using_arrow_keys: bool,
+
+    selected: usize,
+    textures: Vec<Texture2D>,
+    enemy_textures: Vec<Texture2D>,
+    previous_button: Button,
+    next_button: Button,
+    font: Font,
+
+    transform: Animation,
+    // The old character is shown until the transformation swaps it
+    transforming_from: Option<usize>,
+}
+
+impl CharacterGallery {
+    pub async fn new() -> Self {
+        let mut textures = Vec::new();
+        let mut enemy_textures = Vec::new();
+
+        for tier in TIERS.iter() {
+            textures.push(
+                load_texture(format!("res/display/{}.png", tier.name.to_lowercase()).as_str())
+                    .await
+                    .unwrap(),
+            );
+            enemy_textures.push(load_texture(tier.enemy).await.unwrap());
+        }
+
+        Self {
+            current: 0,
+            is_showing_details: true,
+            is_using_arrow_keys: true,
+
+            selected: 0,
+            textures,
+            enemy_textures,
+            previous_button: Button::new(Vec2::ZERO, ARROW_SIZE, "<".to_string(), GRAY, 25).await,
+            next_button: Button::new(Vec2::ZERO, ARROW_SIZE, ">".to_string(), GRAY, 25).await,
+            font: load_ttf_font("res/Roboto-Medium.ttf").await.unwrap(),
+
+            transform: Animation::stopped(
+                &AnimationData::load("res/animations/transform.txt").await,
+            ),
+            transforming_from: None,
+        }
+    }
+
+    // Go back to showing the character the player has
+    pub fn show_current(&mut self) {
+        self.selected = self.current;
+    }
+
+    // Turn the old character into the new one
+    pub fn play_transformation(&mut self, from: usize, to: usize) {
+        self.current = to;
+        self.selected = to;
+        self.transforming_from = Some(from);
+        self.transform.restart();
+    }
+
+    // Must be called once every frame
+    pub fn update(&mut self) {
+        self.transform.update(get_frame_time());
+
+        if self.transform.has_event("swap") || self.transform.is_finished {
+            self.transforming_from = None;
+        }
+
+        self.previous_button.is_disabled = self.selected == 0;
+        self.next_button.is_disabled = self.selected == TIERS.len() - 1;
+
+        self.previous_button.set_rect(layout::place(
+            Anchor::Center,
+            ARROW_SIZE,
+            CHARACTER_OFFSET - vec2(170., 0.),
+        ));
+        self.next_button.set_rect(layout::place(
+            Anchor::Center,
+            ARROW_SIZE,
+            CHARACTER_OFFSET + vec2(170., 0.),
+        ));
+
+        // The arrow keys flip through the characters too
+        let is_left_pressed = self.is_using_arrow_keys && is_key_pressed(KeyCode::Left);
+        let is_right_pressed = self.is_using_arrow_keys && is_key_pressed(KeyCode::Right);
+
+        if (self.previous_button.is_clicked() || is_left_pressed) && self.selected > 0 {
+            self.selected -= 1;
+        }
+
+        if (self.next_button.is_clicked() || is_right_pressed) && self.selected < TIERS.len() - 1 {
+            self.selected += 1;
+        }
+    }
+
+    pub async fn draw(&mut self) {
+        let tier = &TIERS[self.selected];
+        let texture = self.textures[self.transforming_from.unwrap_or(self.selected)];
+        let is_locked = self.selected > self.current;
+
+        let rect = layout::place(
+            Anchor::Center,
+            vec2(texture.width(), texture.height()),
+            CHARACTER_OFFSET,
+        );
+
+        if !self.transform.is_finished {
+            self.transform.draw(texture, rect, WHITE, 0., false);
+        } else {
+            // Locked characters are only a silhouette
+            draw_texture_ex(
+                texture,
+                rect.x,
+                rect.y,
+                if is_locked { BLACK } else { WHITE },
+                DrawTextureParams {
+                    dest_size: Some(rect.size()),
+                    ..Default::default()
+                },
+            );
+        }
+
+        self.previous_button.draw().await;
+        self.next_button.draw().await;
+
+        let status = if self.selected == self.current {
+            "You"
+        } else if is_locked {
+            "Locked"
+        } else {
+            "Owned"
+        };
+
+        layout::draw_text_anchored(
+            format!("{} ({})", tier.name, status).as_str(),
+            Anchor::Center,
+            CHARACTER_OFFSET - vec2(0., 125.),
+            self.font,
+            22,
+            BLACK,
+        );
+
+        if self.is_showing_details {
+            self.draw_details(tier);
+        }
+    }
+
+    fn draw_details(&self, tier: &Tier) {
+        let lines = [
+            format!("Price: ${}", tier.price),
+            format!(
+                "Speed: {}   Fire rate: {}/s   Damage: {}   HP: {}",
+                tier.speed, tier.fire_rate, tier.damage, tier.max_hp
+            ),
+            format!("Ability: {}", tier.ability.name()),
+        ];
+
+        layout::draw_lines(
+            &lines,
+            Anchor::Center,
+            CHARACTER_OFFSET + vec2(0., 125.),
+            25.,
+            self.font,
+            16,
+            BLACK,
+        );
+
+        // Show the enemy next to its name
+        let text_rect = layout::draw_text_anchored(
+            format!("Fights: {}", tier.enemy_name).as_str(),
+            Anchor::Center,
+            CHARACTER_OFFSET + vec2(-ENEMY_ICON_SIZE * 0.5, 125. + lines.len() as f32 * 25.),
+            self.font,
+            16,
+            BLACK,
+        );
+
+        let icon_size = ENEMY_ICON_SIZE * layout::scale();
+
+        draw_texture_ex(
+            self.enemy_textures[self.selected],
+            text_rect.x + text_rect.w + 5. * layout::scale(),
+            text_rect.y + text_rect.h * 0.5 - icon_size * 0.5,
+            WHITE,
+            DrawTextureParams {
+                dest_size: Some(vec2(icon_size, icon_size)),
+                ..Default::default()
+            },
+        );
+    }
+}
@@ -1,2 +1,6 @@
-pub mod bullet_button;
+pub mod bullet_shop;
 pub mod character;
+pub mod character_gallery;
+pub mod stat_tree;
+pub mod tier;
+pub mod upgrades;
@@ -0,0 +1,157 @@
+use crate::objects::button::Button;
+use crate::objects::layout::{self, Anchor};
+use crate::upgrading::upgrades::{Upgrades, STAT_NODES};
+
+use macroquad::prelude::*;
+
+const NODE_SIZE: Vec2 = const_vec2!([180., 50.]);
+const NODE_SPACING: Vec2 = const_vec2!([220., 80.]);
+
+pub struct StatTree {
+    buttons: Vec<Button>,
+
+    pub upgrades: Upgrades,
+    pub money: usize,
+}
+
+impl StatTree {
+    pub async fn new() -> Self {
+        let mut buttons = Vec::new();
+
+        for node in STAT_NODES.iter() {
+            let mut button =
+                Button::new(Vec2::ZERO, NODE_SIZE, node.name.to_string(), GRAY, 13).await;
+
+            button.tooltip = Some(format!(
+                "+{:.0}% {} per level",
+                node.bonus_per_level * 100.,
+                node.name.to_lowercase()
+            ));
+
+            buttons.push(button);
+        }
+
+        Self {
+            buttons,
+
+            upgrades: Upgrades::default(),
+            money: 0,
+        }
+    }
+
+    fn node_rect(branch: usize, depth: usize) -> Rect {
+        // The branches are centred on the screen
+        layout::place(
+            Anchor::Top,
+            NODE_SIZE,
+            vec2(
+                (branch as f32 - 1.) * NODE_SPACING.x,
+                100. + depth as f32 * NODE_SPACING.y,
+            ),
+        )
+    }
+
+    pub fn update(&mut self) {
+        for (node, button) in STAT_NODES.iter().zip(self.buttons.iter_mut()) {
+            button.set_rect(Self::node_rect(node.branch, node.depth));
+
+            // Show the level and price of the node
+            button.text = if self.upgrades.is_maxed(node.stat) {
+                format!("{} (Max)", node.name)
+            } else if let Some((required, level)) = node
+                .requires
+                .filter(|_| !self.upgrades.is_unlocked(node.stat))
+            {
+                // Show what is needed to unlock the node
+                format!(
+                    "{}: needs {} {}",
+                    node.name,
+                    STAT_NODES.iter().find(|r| r.stat == required).unwrap().name,
+                    level
+                )
+            } else {
+                format!(
+                    "{} {}/{}: ${}",
+                    node.name,
+                    self.upgrades.level(node.stat),
+                    node.max_level,
+                    self.upgrades.cost(node.stat)
+                )
+            };
+
+            button.bg = if self.upgrades.is_maxed(node.stat) {
+                GOLD
+            } else if !self.upgrades.is_unlocked(node.stat) {
+                DARKGRAY
+            } else if self.upgrades.can_buy(node.stat, self.money) {
+                LIME
+            } else {
+                GRAY
+            };
+
+            if button.is_clicked() {
+                self.money = self.upgrades.buy(node.stat, self.money);
+            }
+        }
+    }
+
+    pub async fn draw(&mut self) {
+        let depth = STAT_NODES
+            .iter()
+            .map(|node| node.depth)
+            .max()
+            .unwrap_or_default();
+
+        // Draw the background of the tree
+        let background = layout::place(
+            Anchor::Top,
+            vec2(
+                NODE_SPACING.x * 3.,
+                NODE_SPACING.y * depth as f32 + NODE_SIZE.y + 40.,
+            ),
+            vec2(0., 80.),
+        );
+
+        draw_rectangle(
+            background.x,
+            background.y,
+            background.w,
+            background.h,
+            Color::new(0., 0., 0., 0.3),
+        );
+
+        // Draw the lines between nodes and their requirements
+        for node in STAT_NODES.iter() {
+            if let Some((required, _)) = node.requires {
+                let parent = STAT_NODES.iter().find(|r| r.stat == required).unwrap();
+
+                let from = Self::node_rect(parent.branch, parent.depth);
+                let from = vec2(from.x + from.w / 2., from.bottom());
+                let to = Self::node_rect(node.branch, node.depth);
+                let to = vec2(to.x + to.w / 2., to.y);
+
+                draw_line(
+                    from.x,
+                    from.y,
+                    to.x,
+                    to.y,
+                    4.,
+                    if self.upgrades.is_unlocked(node.stat) {
+                        WHITE
+                    } else {
+                        DARKGRAY
+                    },
+                );
+            }
+        }
+
+        for button in self.buttons.iter_mut() {
+            button.draw().await;
+        }
+
+        // Tooltips go over every node
+        for button in self.buttons.iter() {
+            button.draw_tooltip();
+        }
+    }
+}
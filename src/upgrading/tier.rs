@@ -0,0 +1,135 @@
+use crate::battling::player::ability::AbilityKind;
+
+use macroquad::prelude::*;
+
+pub struct Tier {
+    pub name: &'static str,
+    // Price to buy this tier from the previous one
+    pub price: usize,
+
+    // Top walking speed in pixels per second
+    pub speed: f32,
+    // Pixels per second the player speeds up and slows down by every second
+    pub acceleration: f32,
+    pub friction: f32,
+    pub bullet_speed: f32,
+    // Shots per second while the mouse is held down
+    pub fire_rate: f32,
+    pub damage: f32,
+    pub max_hp: usize,
+    pub ability: AbilityKind,
+
+    // The enemy this tier fights
+    pub enemy: &'static str,
+    pub enemy_name: &'static str,
+    // Colour of the juice the enemy splatters when it is killed
+    pub enemy_juice: Color,
+    pub enemy_health: f32,
+    // (Min, Max) money given for a kill
+    pub reward: (usize, usize),
+}
+
+pub const TIERS: [Tier; 5] = [
+    Tier {
+        name: "Apple",
+        price: 0,
+        speed: 250.,
+        acceleration: 1500.,
+        friction: 1800.,
+        bullet_speed: 500.,
+        fire_rate: 3.,
+        damage: 1.,
+        max_hp: 1,
+        ability: AbilityKind::None,
+        enemy: "res/enemy/orange.png",
+        enemy_name: "Orange",
+        enemy_juice: ORANGE,
+        enemy_health: 1.,
+        reward: (1, 10),
+    },
+    Tier {
+        name: "Orange",
+        price: 100,
+        speed: 275.,
+        acceleration: 1800.,
+        friction: 2000.,
+        bullet_speed: 550.,
+        fire_rate: 4.,
+        damage: 1.,
+        max_hp: 2,
+        ability: AbilityKind::Dash,
+        enemy: "res/enemy/pear.png",
+        enemy_name: "Pear",
+        enemy_juice: Color::new(0.7, 0.85, 0.2, 1.),
+        enemy_health: 2.,
+        reward: (5, 50),
+    },
+    Tier {
+        name: "Pear",
+        price: 500,
+        speed: 300.,
+        acceleration: 2000.,
+        friction: 2200.,
+        bullet_speed: 600.,
+        fire_rate: 5.,
+        damage: 2.,
+        max_hp: 3,
+        ability: AbilityKind::ShieldBurst,
+        enemy: "res/enemy/pineapple.png",
+        enemy_name: "Pineapple",
+        enemy_juice: Color::new(1., 0.85, 0.2, 1.),
+        enemy_health: 3.,
+        reward: (10, 100),
+    },
+    Tier {
+        name: "Pineapple",
+        price: 1000,
+        speed: 325.,
+        acceleration: 2200.,
+        friction: 2400.,
+        bullet_speed: 650.,
+        fire_rate: 6.,
+        damage: 2.,
+        max_hp: 4,
+        ability: AbilityKind::SpikyNova,
+        enemy: "res/enemy/banana.png",
+        enemy_name: "Banana",
+        enemy_juice: Color::new(1., 0.95, 0.6, 1.),
+        enemy_health: 4.,
+        reward: (100, 1000),
+    },
+    Tier {
+        name: "Banana",
+        price: 10000,
+        speed: 350.,
+        acceleration: 2400.,
+        // Bananas are slippery
+        friction: 900.,
+        bullet_speed: 700.,
+        fire_rate: 8.,
+        damage: 3.,
+        max_hp: 5,
+        ability: AbilityKind::BananaBoomerang,
+        enemy: "res/monkey.png",
+        enemy_name: "Monkey",
+        enemy_juice: BROWN,
+        enemy_health: 6.,
+        reward: (100, 1000),
+    },
+];
+
+pub fn index(name: &str) -> usize {
+    // Unknown names fall back to the first tier
+    TIERS
+        .iter()
+        .position(|tier| tier.name == name)
+        .unwrap_or_default()
+}
+
+pub fn get(name: &str) -> &'static Tier {
+    &TIERS[index(name)]
+}
+
+pub fn next(name: &str) -> Option<&'static Tier> {
+    TIERS.get(index(name) + 1)
+}
@@ -0,0 +1,222 @@
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Stat {
+    MovementSpeed,
+    PickupMagnet,
+    BulletSpeed,
+    FireRate,
+    MagazineCapacity,
+    ReloadSpeed,
+    MoneyMultiplier,
+}
+
+pub struct StatNode {
+    pub stat: Stat,
+    pub name: &'static str,
+    pub max_level: usize,
+    pub base_cost: usize,
+    // How much the cost grows with every level bought
+    pub cost_growth: f32,
+    // Bonus given by every level (0.1 = +10%)
+    pub bonus_per_level: f32,
+    // (Required stat, Required level)
+    pub requires: Option<(Stat, usize)>,
+
+    // Where the node is in the tree
+    pub branch: usize,
+    pub depth: usize,
+}
+
+pub const STAT_NODES: [StatNode; 7] = [
+    StatNode {
+        stat: Stat::MovementSpeed,
+        name: "Move Speed",
+        max_level: 5,
+        base_cost: 20,
+        cost_growth: 1.8,
+        bonus_per_level: 0.1,
+        requires: None,
+        branch: 0,
+        depth: 0,
+    },
+    StatNode {
+        stat: Stat::PickupMagnet,
+        name: "Magnet",
+        max_level: 5,
+        base_cost: 50,
+        cost_growth: 1.8,
+        bonus_per_level: 0.5,
+        requires: Some((Stat::MovementSpeed, 2)),
+        branch: 0,
+        depth: 1,
+    },
+    StatNode {
+        stat: Stat::BulletSpeed,
+        name: "Bullet Speed",
+        max_level: 5,
+        base_cost: 20,
+        cost_growth: 1.8,
+        bonus_per_level: 0.15,
+        requires: None,
+        branch: 1,
+        depth: 0,
+    },
+    StatNode {
+        stat: Stat::FireRate,
+        name: "Fire Rate",
+        max_level: 5,
+        base_cost: 50,
+        cost_growth: 2.,
+        bonus_per_level: 0.15,
+        requires: Some((Stat::BulletSpeed, 2)),
+        branch: 1,
+        depth: 1,
+    },
+    StatNode {
+        stat: Stat::MagazineCapacity,
+        name: "Magazine",
+        max_level: 5,
+        base_cost: 100,
+        cost_growth: 2.,
+        bonus_per_level: 0.25,
+        requires: Some((Stat::FireRate, 2)),
+        branch: 1,
+        depth: 2,
+    },
+    StatNode {
+        stat: Stat::ReloadSpeed,
+        name: "Reload",
+        max_level: 5,
+        base_cost: 100,
+        cost_growth: 2.,
+        bonus_per_level: 0.2,
+        requires: Some((Stat::MagazineCapacity, 1)),
+        branch: 1,
+        depth: 3,
+    },
+    StatNode {
+        stat: Stat::MoneyMultiplier,
+        name: "Money",
+        max_level: 5,
+        base_cost: 100,
+        cost_growth: 2.5,
+        bonus_per_level: 0.2,
+        requires: None,
+        branch: 2,
+        depth: 0,
+    },
+];
+
+fn node(stat: Stat) -> &'static StatNode {
+    STAT_NODES.iter().find(|node| node.stat == stat).unwrap()
+}
+
+fn node_index(stat: Stat) -> usize {
+    STAT_NODES
+        .iter()
+        .position(|node| node.stat == stat)
+        .unwrap()
+}
+
+#[derive(Clone, Default)]
+pub struct Upgrades {
+    levels: [usize; STAT_NODES.len()],
+}
+
+impl Upgrades {
+    pub fn level(&self, stat: Stat) -> usize {
+        self.levels[node_index(stat)]
+    }
+
+    pub fn is_maxed(&self, stat: Stat) -> bool {
+        self.level(stat) >= node(stat).max_level
+    }
+
+    pub fn is_unlocked(&self, stat: Stat) -> bool {
+        match node(stat).requires {
+            Some((required, level)) => self.level(required) >= level,
+            None => true,
+        }
+    }
+
+    // Price of the next level
+    pub fn cost(&self, stat: Stat) -> usize {
+        let node = node(stat);
+
+        (node.base_cost as f32 * node.cost_growth.powi(self.level(stat) as i32)) as usize
+    }
+
+    pub fn can_buy(&self, stat: Stat, money: usize) -> bool {
+        self.is_unlocked(stat) && !self.is_maxed(stat) && money >= self.cost(stat)
+    }
+
+    // Returns the money left after buying the next level
+    pub fn buy(&mut self, stat: Stat, money: usize) -> usize {
+        if !self.can_buy(stat, money) {
+            return money;
+        }
+
+        let cost = self.cost(stat);
+        self.levels[node_index(stat)] += 1;
+
+        money - cost
+    }
+
+    // Multiplier to apply to the stat (1.0 = no upgrades)
+    pub fn multiplier(&self, stat: Stat) -> f32 {
+        1. + self.level(stat) as f32 * node(stat).bonus_per_level
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cost_grows_with_every_level() {
+        let mut upgrades = Upgrades::default();
+
+        assert_eq!(upgrades.cost(Stat::MovementSpeed), 20);
+        assert_eq!(upgrades.buy(Stat::MovementSpeed, 100), 80);
+        assert_eq!(upgrades.cost(Stat::MovementSpeed), 36);
+        assert_eq!(upgrades.buy(Stat::MovementSpeed, 80), 44);
+        assert_eq!(upgrades.cost(Stat::MovementSpeed), 64);
+    }
+
+    #[test]
+    fn cant_buy_without_enough_money() {
+        let mut upgrades = Upgrades::default();
+
+        assert_eq!(upgrades.buy(Stat::MovementSpeed, 19), 19);
+        assert_eq!(upgrades.level(Stat::MovementSpeed), 0);
+    }
+
+    #[test]
+    fn cant_buy_without_prerequisite() {
+        let mut upgrades = Upgrades::default();
+
+        assert!(!upgrades.is_unlocked(Stat::FireRate));
+        assert_eq!(upgrades.buy(Stat::FireRate, 1000), 1000);
+        assert_eq!(upgrades.level(Stat::FireRate), 0);
+
+        // Fire rate needs bullet speed level 2
+        let money = upgrades.buy(Stat::BulletSpeed, 1000);
+        assert!(!upgrades.is_unlocked(Stat::FireRate));
+
+        let money = upgrades.buy(Stat::BulletSpeed, money);
+        assert!(upgrades.is_unlocked(Stat::FireRate));
+        assert_eq!(upgrades.buy(Stat::FireRate, money), money - 50);
+    }
+
+    #[test]
+    fn cant_buy_past_max_level() {
+        let mut upgrades = Upgrades::default();
+        let mut money = 100_000;
+
+        for _ in 0..node(Stat::MovementSpeed).max_level {
+            money = upgrades.buy(Stat::MovementSpeed, money);
+        }
+
+        assert!(upgrades.is_maxed(Stat::MovementSpeed));
+        assert_eq!(upgrades.buy(Stat::MovementSpeed, money), money);
+    }
+}